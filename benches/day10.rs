@@ -20,3 +20,8 @@ fn part1_bfs() {
 fn part2() {
     part2::process_data(black_box(include_str!("../inputs/day10-input.txt"))).unwrap();
 }
+
+#[divan::bench]
+fn part2_flood() {
+    part2_flood::process_data(black_box(include_str!("../inputs/day10-input.txt"))).unwrap();
+}
@@ -8,7 +8,7 @@ fn main() {
 
 #[divan::bench]
 fn part1() {
-    part1::process_data(black_box(include_str!("../inputs/day11-input.txt")));
+    part1::process_data(black_box(include_str!("../inputs/day11-input.txt"))).unwrap();
 }
 
 #[divan::bench]
@@ -16,5 +16,6 @@ fn part2() {
     part2::process_data(
         black_box(include_str!("../inputs/day11-input.txt")),
         1000000,
-    );
+    )
+    .unwrap();
 }
@@ -8,10 +8,66 @@ fn main() {
 
 #[divan::bench]
 fn part1() {
-    part1::process_data(black_box(include_str!("../inputs/day13-input.txt")));
+    part1::process_data(black_box(include_str!("../inputs/day13-input.txt"))).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    part2::process_data(black_box(include_str!("../inputs/day13-input.txt")));
+    part2::process_data(black_box(include_str!("../inputs/day13-input.txt"))).unwrap();
+}
+
+// the reflection scan alone, on patterns parsed once
+#[divan::bench(args = [0, 1])]
+fn summarize(bencher: divan::Bencher, smudges: usize) {
+    let patterns = pattern::Pattern::parse_all(include_str!("../inputs/day13-input.txt")).unwrap();
+    bencher.bench(|| {
+        patterns
+            .iter()
+            .map(|pattern| pattern.summarize(black_box(smudges)).unwrap())
+            .sum::<usize>()
+    });
+}
+
+// the scan before patterns were packed into bit rows, on the same input: every candidate
+// line clones and reverses the rows above it, and columns are scanned on a transposed copy
+#[divan::bench(args = [0, 1])]
+fn summarize_cloned(bencher: divan::Bencher, smudges: usize) {
+    let patterns = include_str!("../inputs/day13-input.txt")
+        .split("\n\n")
+        .map(|s| s.lines().map(|s| s.chars().collect()).collect())
+        .collect::<Vec<Vec<Vec<char>>>>();
+    bencher.bench(|| {
+        patterns
+            .iter()
+            .map(|pattern| {
+                let smudges = black_box(smudges);
+                mirror_count(pattern, smudges) * 100 + mirror_count(&transpose(pattern), smudges)
+            })
+            .sum::<usize>()
+    });
+}
+
+fn mirror_count(pattern: &[Vec<char>], smudges: usize) -> usize {
+    for i in 1..pattern.len() {
+        let mut above = pattern[..i].to_vec();
+        above.reverse();
+        let below = &pattern[i..];
+
+        let len = above.len().min(below.len());
+        let differences = above[..len]
+            .iter()
+            .zip(&below[..len])
+            .map(|(a, b)| a.iter().zip(b).filter(|(ac, bc)| ac != bc).count())
+            .sum::<usize>();
+        if differences == smudges {
+            return i;
+        }
+    }
+    0
+}
+
+fn transpose(pattern: &[Vec<char>]) -> Vec<Vec<char>> {
+    (0..pattern[0].len())
+        .map(|j| pattern.iter().map(|row| row[j]).collect())
+        .collect()
 }
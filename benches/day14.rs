@@ -8,10 +8,10 @@ fn main() {
 
 #[divan::bench]
 fn part1() {
-    part1::process_data(black_box(include_str!("../inputs/day14-input.txt")));
+    part1::process_data(black_box(include_str!("../inputs/day14-input.txt"))).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    part2::process_data(black_box(include_str!("../inputs/day14-input.txt")));
+    part2::process_data(black_box(include_str!("../inputs/day14-input.txt"))).unwrap();
 }
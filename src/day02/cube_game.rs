@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use nom::{
+    character::complete::{self, alpha1, space0, space1},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{delimited, separated_pair},
+    IResult, Parser,
+};
+use nom_supreme::{tag::complete::tag, ParserExt};
+
+// colour name -> number of cubes
+pub type CubeSet<'a> = BTreeMap<&'a str, u32>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game<'a> {
+    pub id: u32,
+    pub reveals: Vec<CubeSet<'a>>,
+}
+
+impl<'a> Game<'a> {
+    // a colour missing from the bag means there is no cube of that colour
+    pub fn is_feasible(&self, bag: &CubeSet) -> bool {
+        self.reveals.iter().all(|reveal| {
+            reveal
+                .iter()
+                .all(|(colour, &count)| count <= bag.get(colour).copied().unwrap_or(0))
+        })
+    }
+
+    pub fn minimal_bag(&self) -> CubeSet<'a> {
+        self.reveals
+            .iter()
+            .flatten()
+            .fold(CubeSet::new(), |mut bag, (&colour, &count)| {
+                let max = bag.entry(colour).or_insert(0);
+                *max = (*max).max(count);
+                bag
+            })
+    }
+
+    pub fn power(&self, colours: &[&str]) -> u32 {
+        let bag = self.minimal_bag();
+        colours
+            .iter()
+            .map(|colour| bag.get(colour).copied().unwrap_or(0))
+            .product()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CubeGame<'a> {
+    games: Vec<Game<'a>>,
+}
+
+impl<'a> CubeGame<'a> {
+    pub fn parse(input: &'a str) -> Result<Self> {
+        let games = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| {
+                all_consuming(parse_game)
+                    .parse(line.trim())
+                    .map(|(_, game)| game)
+                    .map_err(|e| anyhow!("Failed to parse game on line {}: {}", idx + 1, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { games })
+    }
+
+    pub fn games(&self) -> &[Game<'a>] {
+        &self.games
+    }
+
+    pub fn game(&self, id: u32) -> Option<&Game<'a>> {
+        self.games.iter().find(|game| game.id == id)
+    }
+
+    pub fn feasible_games<'b>(&'b self, bag: &'b CubeSet) -> impl Iterator<Item = &'b Game<'a>> {
+        self.games.iter().filter(move |game| game.is_feasible(bag))
+    }
+
+    pub fn minimal_bags(&self) -> Vec<(u32, CubeSet<'a>)> {
+        self.games
+            .iter()
+            .map(|game| (game.id, game.minimal_bag()))
+            .collect()
+    }
+
+    // ids of the games which are not feasible with `bag`,
+    // but become feasible once `colour` is increased by `extra` cubes
+    pub fn unlocked_by_increase<'b>(
+        &self,
+        bag: &CubeSet<'b>,
+        colour: &'b str,
+        extra: u32,
+    ) -> Vec<u32> {
+        let mut increased = bag.clone();
+        let count = increased.entry(colour).or_insert(0);
+        *count = count.saturating_add(extra);
+
+        self.games
+            .iter()
+            .filter(|game| !game.is_feasible(bag) && game.is_feasible(&increased))
+            .map(|game| game.id)
+            .collect()
+    }
+}
+
+fn parse_game(input: &str) -> IResult<&str, Game<'_>> {
+    separated_pair(
+        tag("Game").precedes(space1).precedes(complete::u32),
+        delimited(space0, complete::char(':'), space0),
+        separated_list1(delimited(space0, complete::char(';'), space0), parse_reveal),
+    )
+    .map(|(id, reveals)| Game { id, reveals })
+    .parse(input)
+}
+
+fn parse_reveal(input: &str) -> IResult<&str, CubeSet<'_>> {
+    separated_list1(
+        delimited(space0, complete::char(','), space0),
+        separated_pair(complete::u32, space1, alpha1),
+    )
+    .map(|cubes| {
+        cubes
+            .into_iter()
+            .fold(CubeSet::new(), |mut reveal, (count, colour)| {
+                *reveal.entry(colour).or_insert(0) += count;
+                reveal
+            })
+    })
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    fn bag<'a>(cubes: &[(&'a str, u32)]) -> CubeSet<'a> {
+        cubes.iter().copied().collect()
+    }
+
+    #[test]
+    fn it_parses_every_reveal() {
+        let cube_game = CubeGame::parse(INPUT).unwrap();
+        assert_eq!(5, cube_game.games().len());
+
+        let game = cube_game.game(1).unwrap();
+        assert_eq!(
+            vec![
+                bag(&[("blue", 3), ("red", 4)]),
+                bag(&[("red", 1), ("green", 2), ("blue", 6)]),
+                bag(&[("green", 2)]),
+            ],
+            game.reveals
+        );
+    }
+
+    #[rstest]
+    #[case("Game 1 3 blue")]
+    #[case("Game 1: 3")]
+    #[case("Game one: 3 blue")]
+    #[case("Game 1: 3 blue; ")]
+    fn it_rejects_malformed_games(#[case] input: &str) {
+        assert!(CubeGame::parse(input).is_err());
+    }
+
+    #[test]
+    fn it_reports_the_failing_line() {
+        let err = CubeGame::parse("Game 1: 3 blue\nGame 2: blue").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn it_supports_arbitrary_colours() {
+        let cube_game = CubeGame::parse("Game 7: 2 cyan, 1 magenta; 4 cyan").unwrap();
+        let game = cube_game.game(7).unwrap();
+        assert_eq!(bag(&[("cyan", 4), ("magenta", 1)]), game.minimal_bag());
+        assert!(game.is_feasible(&bag(&[("cyan", 4), ("magenta", 1)])));
+        assert!(!game.is_feasible(&bag(&[("cyan", 4)])));
+    }
+
+    #[test]
+    fn it_finds_feasible_games() {
+        let cube_game = CubeGame::parse(INPUT).unwrap();
+        let loaded = bag(&[("red", 12), ("green", 13), ("blue", 14)]);
+        let ids = cube_game
+            .feasible_games(&loaded)
+            .map(|game| game.id)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![1, 2, 5], ids);
+    }
+
+    #[test]
+    fn it_computes_minimal_bags_and_power() {
+        let cube_game = CubeGame::parse(INPUT).unwrap();
+        let bags = cube_game.minimal_bags();
+        assert_eq!((1, bag(&[("red", 4), ("green", 2), ("blue", 6)])), bags[0]);
+
+        let powers = cube_game
+            .games()
+            .iter()
+            .map(|game| game.power(&["red", "green", "blue"]))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![48, 12, 1560, 630, 36], powers);
+    }
+
+    #[rstest]
+    #[case("red", 8, vec![3])]
+    #[case("red", 7, vec![])]
+    #[case("blue", 1, vec![])]
+    fn it_finds_games_unlocked_by_increase(
+        #[case] colour: &str,
+        #[case] extra: u32,
+        #[case] expected: Vec<u32>,
+    ) {
+        let cube_game = CubeGame::parse(INPUT).unwrap();
+        let loaded = bag(&[("red", 12), ("green", 13), ("blue", 14)]);
+        assert_eq!(
+            expected,
+            cube_game.unlocked_by_increase(&loaded, colour, extra)
+        );
+    }
+}
@@ -1,2 +1,4 @@
+pub mod cube_game;
 pub mod part1;
 pub mod part2;
+pub mod stats;
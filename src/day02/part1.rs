@@ -1,74 +1,17 @@
-use regex::Regex;
-
-#[derive(Debug)]
-struct Game {
-    red: u32,
-    green: u32,
-    blue: u32,
-}
+use super::cube_game::{CubeGame, CubeSet};
 
 pub fn process_data(input: &str) -> anyhow::Result<u32> {
-    let loaded_game = Game {
-        red: 12,
-        green: 13,
-        blue: 14,
-    };
-    // Game (\d+):(.+?);(.+?);(.+)
-    let re = Regex::new(r"Game (?<num>\d+):(?<rounds>.+)")?;
-
-    let num = input
-        .lines()
-        .flat_map(|s| {
-            let caps = re.captures(s)?;
-            let game_num: &str = &caps["num"];
-            let rounds_in_str = &caps["rounds"];
-            let round_games = parse_game(rounds_in_str).ok()?;
-
-            for game in round_games {
-                if game.red > loaded_game.red {
-                    return None;
-                }
-                if game.green > loaded_game.green {
-                    return None;
-                }
-                if game.blue > loaded_game.blue {
-                    return None;
-                }
-            }
+    let loaded_bag = CubeSet::from([("red", 12), ("green", 13), ("blue", 14)]);
+    let cube_game = CubeGame::parse(input)?;
 
-            game_num.parse::<u32>().ok()
-        })
+    let num = cube_game
+        .feasible_games(&loaded_bag)
+        .map(|game| game.id)
         .sum();
 
     Ok(num)
 }
 
-fn parse_game(rounds_in_str: &str) -> anyhow::Result<Vec<Game>> {
-    let mut games = Vec::new();
-    for round in rounds_in_str.split(';') {
-        let (mut red, mut green, mut blue) = (0, 0, 0);
-        for item in round.split(", ") {
-            let parts: Vec<&str> = item.split_whitespace().collect();
-            let count = parts[0].parse::<u32>()?;
-            match parts[1] {
-                "red" => {
-                    red = count;
-                }
-                "blue" => {
-                    blue = count;
-                }
-                "green" => {
-                    green = count;
-                }
-                _ => (),
-            }
-            games.push(Game { red, blue, green });
-        }
-    }
-
-    Ok(games)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
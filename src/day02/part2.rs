@@ -1,52 +1,17 @@
-use regex::Regex;
-
-#[derive(Debug)]
-struct Game {
-    red: u32,
-    green: u32,
-    blue: u32,
-}
+use super::cube_game::CubeGame;
 
 pub fn process_data(input: &str) -> anyhow::Result<u32> {
-    // Game (\d+):(.+?);(.+?);(.+)
-    let re = Regex::new(r"Game \d+:(?<rounds>.+)")?;
+    let cube_game = CubeGame::parse(input)?;
 
-    let num = input
-        .lines()
-        .flat_map(|s| {
-            let caps = re.captures(s)?;
-            let rounds_in_str = &caps["rounds"];
-            let game = parse_game(rounds_in_str).ok()?;
-            let power = game.red * game.green * game.blue;
-            Some(power)
-        })
+    let num = cube_game
+        .games()
+        .iter()
+        .map(|game| game.power(&["red", "green", "blue"]))
         .sum();
 
     Ok(num)
 }
 
-fn parse_game(rounds_in_str: &str) -> anyhow::Result<Game> {
-    let (mut red, mut green, mut blue) = (0, 0, 0);
-    for round in rounds_in_str.split(';') {
-        for item in round.split(", ") {
-            let parts: Vec<&str> = item.split_whitespace().collect();
-            let count = parts[0].parse::<u32>()?;
-            match parts[1] {
-                "red" => {
-                    red = red.max(count);
-                }
-                "blue" => blue = blue.max(count),
-                "green" => {
-                    green = green.max(count);
-                }
-                _ => (),
-            }
-        }
-    }
-
-    Ok(Game { red, green, blue })
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
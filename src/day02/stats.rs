@@ -0,0 +1,301 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use super::cube_game::{CubeGame, CubeSet};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColourStats {
+    pub max: u32,
+    // a reveal without the colour counts as 0 cubes
+    pub mean: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats<'a> {
+    pub game_count: usize,
+    pub reveal_count: usize,
+    pub colours: BTreeMap<&'a str, ColourStats>,
+    // (game id, minimal bag) of the game which needs the most cubes
+    pub largest_bag: Option<(u32, CubeSet<'a>)>,
+    pub coverage_percent: u32,
+    pub smallest_bag: CubeSet<'a>,
+}
+
+impl<'a> Stats<'a> {
+    pub fn new(cube_game: &CubeGame<'a>, coverage_percent: u32) -> Self {
+        let reveals = cube_game
+            .games()
+            .iter()
+            .flat_map(|game| game.reveals.iter())
+            .collect::<Vec<_>>();
+
+        let mut colours: BTreeMap<&'a str, ColourStats> = BTreeMap::new();
+        for (&colour, &count) in reveals.iter().copied().flatten() {
+            let stats = colours
+                .entry(colour)
+                .or_insert(ColourStats { max: 0, mean: 0.0 });
+            stats.max = stats.max.max(count);
+            stats.mean += count as f64;
+        }
+        colours
+            .values_mut()
+            .for_each(|stats| stats.mean /= reveals.len() as f64);
+
+        let mut largest_bag: Option<(u32, CubeSet<'a>)> = None;
+        for (id, bag) in cube_game.minimal_bags() {
+            let is_larger = match &largest_bag {
+                Some((_, largest)) => cube_count(&bag) > cube_count(largest),
+                None => true,
+            };
+            if is_larger {
+                largest_bag = Some((id, bag));
+            }
+        }
+
+        Self {
+            game_count: cube_game.games().len(),
+            reveal_count: reveals.len(),
+            colours,
+            largest_bag,
+            coverage_percent,
+            smallest_bag: smallest_bag_for_coverage(cube_game, coverage_percent),
+        }
+    }
+
+    // the same figures as `Display`, as a JSON object
+    pub fn to_json(&self) -> String {
+        let colours = self
+            .colours
+            .iter()
+            .map(|(colour, stats)| {
+                format!(
+                    "{}:{{\"max\":{},\"mean\":{}}}",
+                    json_string(colour),
+                    stats.max,
+                    stats.mean
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let largest_bag = match &self.largest_bag {
+            Some((id, bag)) => format!("{{\"game\":{},\"bag\":{}}}", id, json_bag(bag)),
+            None => "null".to_string(),
+        };
+
+        format!(
+            "{{\"game_count\":{},\"reveal_count\":{},\"colours\":{{{}}},\"largest_bag\":{},\"coverage_percent\":{},\"smallest_bag\":{}}}",
+            self.game_count,
+            self.reveal_count,
+            colours,
+            largest_bag,
+            self.coverage_percent,
+            json_bag(&self.smallest_bag)
+        )
+    }
+}
+
+impl Display for Stats<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "games: {}, reveals: {}",
+            self.game_count, self.reveal_count
+        )?;
+        for (colour, stats) in self.colours.iter() {
+            writeln!(f, "{}: max {}, mean {:.2}", colour, stats.max, stats.mean)?;
+        }
+        if let Some((id, bag)) = &self.largest_bag {
+            writeln!(f, "largest bag: game {} ({})", id, format_bag(bag))?;
+        }
+        write!(
+            f,
+            "smallest bag for {}% of games: {}",
+            self.coverage_percent,
+            format_bag(&self.smallest_bag)
+        )
+    }
+}
+
+// The bag with the fewest cubes in total which makes at least `percent` of the games possible.
+// Only the counts of the per-game minimal bags can be optimal for a colour,
+// so the search walks those combinations and prunes by the best total found so far.
+pub fn smallest_bag_for_coverage<'a>(cube_game: &CubeGame<'a>, percent: u32) -> CubeSet<'a> {
+    let minimal_bags = cube_game
+        .minimal_bags()
+        .into_iter()
+        .map(|(_, bag)| bag)
+        .collect::<Vec<_>>();
+    let required = (minimal_bags.len() * percent.min(100) as usize).div_ceil(100);
+
+    let mut candidates: BTreeMap<&'a str, Vec<u32>> = BTreeMap::new();
+    for (&colour, &count) in minimal_bags.iter().flatten() {
+        candidates
+            .entry(colour)
+            .or_insert_with(|| vec![0])
+            .push(count);
+    }
+    let candidates = candidates
+        .into_iter()
+        .map(|(colour, mut counts)| {
+            counts.sort();
+            counts.dedup();
+            (colour, counts)
+        })
+        .collect::<Vec<_>>();
+
+    let mut search = CoverageSearch {
+        minimal_bags: &minimal_bags,
+        candidates: &candidates,
+        required,
+        best: None,
+    };
+    search.run(&mut Vec::with_capacity(candidates.len()), 0);
+
+    let chosen = search.best.map(|(_, counts)| counts).unwrap_or_default();
+    candidates
+        .iter()
+        .zip(chosen)
+        .map(|((colour, _), count)| (*colour, count))
+        .collect()
+}
+
+struct CoverageSearch<'s, 'a> {
+    minimal_bags: &'s [CubeSet<'a>],
+    candidates: &'s [(&'a str, Vec<u32>)],
+    required: usize,
+    best: Option<(u32, Vec<u32>)>,
+}
+
+impl CoverageSearch<'_, '_> {
+    fn run(&mut self, chosen: &mut Vec<u32>, total: u32) {
+        let idx = chosen.len();
+        if idx == self.candidates.len() {
+            if self.covered(chosen) >= self.required {
+                self.best = Some((total, chosen.clone()));
+            }
+            return;
+        }
+
+        for &count in self.candidates[idx].1.iter() {
+            let total = total + count;
+            if matches!(self.best, Some((best, _)) if total >= best) {
+                break;
+            }
+            chosen.push(count);
+            self.run(chosen, total);
+            chosen.pop();
+        }
+    }
+
+    fn covered(&self, chosen: &[u32]) -> usize {
+        self.minimal_bags
+            .iter()
+            .filter(|bag| {
+                self.candidates
+                    .iter()
+                    .zip(chosen)
+                    .all(|((colour, _), &count)| bag.get(colour).copied().unwrap_or(0) <= count)
+            })
+            .count()
+    }
+}
+
+fn cube_count(bag: &CubeSet) -> u32 {
+    bag.values().sum()
+}
+
+fn format_bag(bag: &CubeSet) -> String {
+    bag.iter()
+        .map(|(colour, count)| format!("{} {}", colour, count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn json_bag(bag: &CubeSet) -> String {
+    let counts = bag
+        .iter()
+        .map(|(colour, count)| format!("{}:{}", json_string(colour), count))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{{{}}}", counts)
+}
+
+fn json_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn it_collects_colour_stats() {
+        let cube_game = CubeGame::parse(INPUT).unwrap();
+        let stats = Stats::new(&cube_game, 100);
+
+        assert_eq!(5, stats.game_count);
+        assert_eq!(14, stats.reveal_count);
+        assert_eq!(15, stats.colours["blue"].max);
+        assert_eq!(20, stats.colours["red"].max);
+        assert_eq!(13, stats.colours["green"].max);
+        assert!((stats.colours["red"].mean - 61.0 / 14.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn it_finds_the_largest_bag() {
+        let cube_game = CubeGame::parse(INPUT).unwrap();
+        let (id, bag) = Stats::new(&cube_game, 100).largest_bag.unwrap();
+        assert_eq!(3, id);
+        assert_eq!(
+            CubeSet::from([("red", 20), ("green", 13), ("blue", 6)]),
+            bag
+        );
+    }
+
+    #[rstest]
+    #[case(0, [("blue", 0), ("green", 0), ("red", 0)])]
+    #[case(20, [("blue", 4), ("green", 3), ("red", 1)])]
+    #[case(60, [("blue", 6), ("green", 3), ("red", 6)])]
+    #[case(100, [("blue", 15), ("green", 13), ("red", 20)])]
+    fn it_finds_the_smallest_bag_for_coverage(
+        #[case] percent: u32,
+        #[case] expected: [(&str, u32); 3],
+    ) {
+        let cube_game = CubeGame::parse(INPUT).unwrap();
+        let bag = smallest_bag_for_coverage(&cube_game, percent);
+        assert_eq!(CubeSet::from(expected), bag);
+
+        let covered = cube_game.feasible_games(&bag).count();
+        assert!(covered * 100 >= cube_game.games().len() * percent as usize);
+    }
+
+    #[test]
+    fn it_displays_the_stats() {
+        let cube_game = CubeGame::parse(INPUT).unwrap();
+        let output = Stats::new(&cube_game, 100).to_string();
+        assert!(output.contains("largest bag: game 3 (blue 6, green 13, red 20)"));
+        assert!(output.contains("smallest bag for 100% of games: blue 15, green 13, red 20"));
+    }
+
+    #[test]
+    fn it_serialises_the_stats_as_json() {
+        let cube_game = CubeGame::parse(INPUT).unwrap();
+        let json = Stats::new(&cube_game, 100).to_json();
+        assert!(json.starts_with(
+            "{\"game_count\":5,\"reveal_count\":14,\"colours\":{\"blue\":{\"max\":15,"
+        ));
+        assert!(json
+            .contains("\"largest_bag\":{\"game\":3,\"bag\":{\"blue\":6,\"green\":13,\"red\":20}}"));
+        assert!(json.ends_with(
+            "\"coverage_percent\":100,\"smallest_bag\":{\"blue\":15,\"green\":13,\"red\":20}}"
+        ));
+        assert_eq!("\"a\\\"b\"", json_string("a\"b"));
+    }
+}
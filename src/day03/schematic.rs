@@ -0,0 +1,315 @@
+use std::{collections::HashMap, ops::Range};
+
+use anyhow::{anyhow, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub row: usize,
+    pub cols: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub kind: char,
+    pub row: usize,
+    pub col: usize,
+}
+
+// a gear is a symbol of `kind` touching exactly `numbers` part numbers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule {
+    pub kind: char,
+    pub numbers: usize,
+}
+
+impl Default for GearRule {
+    fn default() -> Self {
+        Self {
+            kind: '*',
+            numbers: 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Schematic {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    // bipartite adjacency, indexed by number / symbol position
+    number_to_symbols: Vec<Vec<usize>>,
+    symbol_to_numbers: Vec<Vec<usize>>,
+}
+
+impl Schematic {
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut schematic = Self::default();
+        let mut symbol_positions = HashMap::new();
+
+        for (row, line) in input.lines().enumerate() {
+            let line = line.as_bytes();
+            let mut col = 0;
+            while col < line.len() {
+                let c = line[col] as char;
+                if c.is_ascii_digit() {
+                    let start = col;
+                    while col < line.len() && line[col].is_ascii_digit() {
+                        col += 1;
+                    }
+                    let digits = std::str::from_utf8(&line[start..col])?;
+                    let value = digits.parse::<u32>().map_err(|e| {
+                        anyhow!("Invalid number {} at {}:{}: {}", digits, row, start, e)
+                    })?;
+                    schematic.numbers.push(Number {
+                        value,
+                        row,
+                        cols: start..col,
+                    });
+                    continue;
+                }
+
+                if is_symbol(c) {
+                    symbol_positions.insert((row, col), schematic.symbols.len());
+                    schematic.symbols.push(Symbol { kind: c, row, col });
+                }
+                col += 1;
+            }
+        }
+
+        schematic.number_to_symbols = vec![Vec::new(); schematic.numbers.len()];
+        schematic.symbol_to_numbers = vec![Vec::new(); schematic.symbols.len()];
+        for (number_idx, number) in schematic.numbers.iter().enumerate() {
+            for row in number.row.saturating_sub(1)..=number.row + 1 {
+                for col in number.cols.start.saturating_sub(1)..=number.cols.end {
+                    if let Some(&symbol_idx) = symbol_positions.get(&(row, col)) {
+                        schematic.number_to_symbols[number_idx].push(symbol_idx);
+                        schematic.symbol_to_numbers[symbol_idx].push(number_idx);
+                    }
+                }
+            }
+        }
+
+        Ok(schematic)
+    }
+
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    pub fn symbols_adjacent_to_number(&self, number_idx: usize) -> impl Iterator<Item = &Symbol> {
+        self.number_to_symbols[number_idx]
+            .iter()
+            .map(|&idx| &self.symbols[idx])
+    }
+
+    pub fn numbers_adjacent_to_symbol(&self, symbol_idx: usize) -> impl Iterator<Item = &Number> {
+        self.symbol_to_numbers[symbol_idx]
+            .iter()
+            .map(|&idx| &self.numbers[idx])
+    }
+
+    // numbers adjacent to at least one symbol
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers_where(|symbols| !symbols.is_empty())
+    }
+
+    // numbers adjacent to no symbol at all
+    pub fn orphaned_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers_where(|symbols| symbols.is_empty())
+    }
+
+    // every number touching a symbol of `kind` is yielded once,
+    // even if it touches several of them
+    pub fn numbers_adjacent_to(&self, kind: char) -> impl Iterator<Item = &Number> {
+        self.numbers_where(move |symbols| {
+            symbols
+                .iter()
+                .any(|&symbol_idx| self.symbols[symbol_idx].kind == kind)
+        })
+    }
+
+    pub fn symbols_touching(&self, count: usize) -> impl Iterator<Item = &Symbol> {
+        self.symbols
+            .iter()
+            .zip(self.symbol_to_numbers.iter())
+            .filter(move |(_, numbers)| numbers.len() == count)
+            .map(|(symbol, _)| symbol)
+    }
+
+    // gears together with the numbers they touch,
+    // so a number touching two gears shows up under both
+    pub fn gears(&self, rule: &GearRule) -> Vec<(&Symbol, Vec<&Number>)> {
+        self.symbols
+            .iter()
+            .enumerate()
+            .filter(|(idx, symbol)| {
+                symbol.kind == rule.kind && self.symbol_to_numbers[*idx].len() == rule.numbers
+            })
+            .map(|(idx, symbol)| (symbol, self.numbers_adjacent_to_symbol(idx).collect()))
+            .collect()
+    }
+
+    // a rule with many numbers can overflow the ratios, which is an error
+    pub fn gear_ratio_sum(&self, rule: &GearRule) -> Result<u64> {
+        self.gears(rule)
+            .iter()
+            .try_fold(0u64, |sum, (symbol, numbers)| {
+                numbers
+                    .iter()
+                    .try_fold(1u64, |ratio, n| ratio.checked_mul(n.value as u64))
+                    .and_then(|ratio| sum.checked_add(ratio))
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "Gear ratio sum overflows at the gear in row {}, column {}",
+                            symbol.row,
+                            symbol.col
+                        )
+                    })
+            })
+    }
+
+    fn numbers_where<'s>(
+        &'s self,
+        predicate: impl Fn(&[usize]) -> bool + 's,
+    ) -> impl Iterator<Item = &'s Number> {
+        self.numbers
+            .iter()
+            .zip(self.number_to_symbols.iter())
+            .filter(move |(_, symbols)| predicate(symbols))
+            .map(|(number, _)| number)
+    }
+}
+
+fn is_symbol(c: char) -> bool {
+    c.is_ascii_punctuation() && c != '.'
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    fn values<'a>(numbers: impl Iterator<Item = &'a Number>) -> Vec<u32> {
+        numbers.map(|n| n.value).collect()
+    }
+
+    #[test]
+    fn it_extracts_numbers_with_spans() {
+        let schematic = Schematic::parse(INPUT).unwrap();
+        assert_eq!(10, schematic.numbers().len());
+        assert_eq!(
+            Number {
+                value: 114,
+                row: 0,
+                cols: 5..8
+            },
+            schematic.numbers()[1]
+        );
+        assert_eq!(6, schematic.symbols().len());
+    }
+
+    #[test]
+    fn it_finds_part_and_orphaned_numbers() {
+        let schematic = Schematic::parse(INPUT).unwrap();
+        assert_eq!(4361u32, schematic.part_numbers().map(|n| n.value).sum());
+        assert_eq!(vec![114, 58], values(schematic.orphaned_numbers()));
+    }
+
+    #[rstest]
+    #[case('*', vec![467, 35, 617, 755, 598])]
+    #[case('#', vec![633])]
+    #[case('$', vec![664])]
+    #[case('@', vec![])]
+    fn it_finds_numbers_adjacent_to_kind(#[case] kind: char, #[case] expected: Vec<u32>) {
+        let schematic = Schematic::parse(INPUT).unwrap();
+        assert_eq!(expected, values(schematic.numbers_adjacent_to(kind)));
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(1, 4)]
+    #[case(2, 2)]
+    fn it_finds_symbols_touching_n_numbers(#[case] count: usize, #[case] expected: usize) {
+        let schematic = Schematic::parse(INPUT).unwrap();
+        assert_eq!(expected, schematic.symbols_touching(count).count());
+    }
+
+    #[test]
+    fn it_sums_gear_ratios() {
+        let schematic = Schematic::parse(INPUT).unwrap();
+        assert_eq!(
+            467835,
+            schematic.gear_ratio_sum(&GearRule::default()).unwrap()
+        );
+
+        let rule = GearRule {
+            kind: '*',
+            numbers: 1,
+        };
+        assert_eq!(617, schematic.gear_ratio_sum(&rule).unwrap());
+    }
+
+    #[test]
+    fn it_counts_a_number_towards_every_gear_it_touches() {
+        let schematic = Schematic::parse(
+            "2*3
+.4.
+5*6",
+        )
+        .unwrap();
+        let gears = schematic.gears(&GearRule {
+            kind: '*',
+            numbers: 3,
+        });
+        assert_eq!(2, gears.len());
+        assert_eq!(
+            2 * 3 * 4 + 4 * 5 * 6,
+            schematic
+                .gear_ratio_sum(&GearRule {
+                    kind: '*',
+                    numbers: 3,
+                })
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn it_reports_overflowing_gear_ratios() {
+        let schematic = Schematic::parse(
+            "4000000000*4000000000
+.........4000000000..",
+        )
+        .unwrap();
+        let rule = GearRule {
+            kind: '*',
+            numbers: 3,
+        };
+        assert_eq!(1, schematic.gears(&rule).len());
+        let error = schematic.gear_ratio_sum(&rule).unwrap_err();
+        assert_eq!(
+            "Gear ratio sum overflows at the gear in row 0, column 10",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn it_rejects_numbers_out_of_range() {
+        assert!(Schematic::parse("99999999999*").is_err());
+    }
+}
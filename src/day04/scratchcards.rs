@@ -0,0 +1,266 @@
+use std::{cmp::Ordering, collections::VecDeque};
+
+use anyhow::{anyhow, Result};
+use nom::{
+    character::complete::{self, space0, space1},
+    combinator::all_consuming,
+    multi::many0,
+    sequence::{preceded, tuple},
+    IResult, Parser,
+};
+use nom_supreme::{tag::complete::tag, ParserExt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub id: u32,
+    // both sorted, `winning` without duplicates
+    pub winning: Vec<u32>,
+    pub owned: Vec<u32>,
+}
+
+impl Card {
+    pub fn parse(input: &str) -> Result<Self> {
+        all_consuming(parse_card)
+            .parse(input.trim())
+            .map(|(_, card)| card)
+            .map_err(|e| anyhow!("Failed to parse card {:?}: {}", input, e))
+    }
+
+    pub fn matches(&self) -> usize {
+        let (mut i, mut j) = (0, 0);
+        let mut counter = 0;
+
+        while i < self.winning.len() && j < self.owned.len() {
+            match self.winning[i].cmp(&self.owned[j]) {
+                Ordering::Less => i += 1,
+                Ordering::Equal => {
+                    i += 1;
+                    j += 1;
+                    counter += 1;
+                }
+                Ordering::Greater => j += 1,
+            }
+        }
+
+        counter
+    }
+
+    // The first match makes the card worth one point
+    // and each match after the first doubles the point value of that card
+    pub fn points(&self) -> Result<u128> {
+        match self.matches() {
+            0 => Ok(0),
+            n => u32::try_from(n - 1)
+                .ok()
+                .and_then(|shift| 1u128.checked_shl(shift))
+                .ok_or_else(|| {
+                    anyhow!("Card {} is worth 2^{} points, beyond u128", self.id, n - 1)
+                }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scratchcards {
+    cards: Vec<Card>,
+}
+
+impl Scratchcards {
+    pub fn parse(input: &str) -> Result<Self> {
+        let cards = stream_cards(input).collect::<Result<Vec<_>>>()?;
+        Ok(Self { cards })
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn match_counts(&self) -> Vec<usize> {
+        self.cards.iter().map(Card::matches).collect()
+    }
+
+    pub fn total_points(&self) -> Result<u128> {
+        self.cards.iter().try_fold(0u128, |total, card| {
+            total
+                .checked_add(card.points()?)
+                .ok_or_else(|| anyhow!("Total points overflow u128 at card {}", card.id))
+        })
+    }
+
+    // number of instances of each card, originals included
+    pub fn copies(&self) -> Result<Vec<u128>> {
+        cascade(self.cards.iter().map(Card::matches)).collect()
+    }
+
+    pub fn total_cards(&self) -> Result<u128> {
+        cascade(self.cards.iter().map(Card::matches)).try_fold(0u128, |total, copies| {
+            total
+                .checked_add(copies?)
+                .ok_or_else(|| anyhow!("Total number of cards overflows u128"))
+        })
+    }
+}
+
+// parses one card per non-empty line, lazily
+pub fn stream_cards(input: &str) -> impl Iterator<Item = Result<Card>> + '_ {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(Card::parse)
+}
+
+// Turns the match count of each card into its number of instances in a single forward pass.
+// Only the copies won for the next `matches` cards are kept around,
+// so memory stays bounded by the largest match count instead of the number of cards.
+// Once a count overflows u128 the iterator yields an error and stops.
+pub fn cascade<I: IntoIterator<Item = usize>>(matches: I) -> Cascade<I::IntoIter> {
+    Cascade {
+        matches: matches.into_iter(),
+        pending: VecDeque::new(),
+        card: 0,
+        overflowed: false,
+    }
+}
+
+pub struct Cascade<I> {
+    matches: I,
+    pending: VecDeque<u128>,
+    // the number of cards seen so far
+    card: usize,
+    overflowed: bool,
+}
+
+impl<I: Iterator<Item = usize>> Iterator for Cascade<I> {
+    type Item = Result<u128>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.overflowed {
+            return None;
+        }
+        let matches = self.matches.next()?;
+        self.card += 1;
+        let won = self.pending.pop_front().unwrap_or(0);
+
+        if self.pending.len() < matches {
+            self.pending.resize(matches, 0);
+        }
+        let copies = won.checked_add(1).filter(|&copies| {
+            self.pending
+                .iter_mut()
+                .take(matches)
+                .all(|pending| match pending.checked_add(copies) {
+                    Some(sum) => {
+                        *pending = sum;
+                        true
+                    }
+                    None => false,
+                })
+        });
+        if copies.is_none() {
+            self.overflowed = true;
+        }
+
+        Some(copies.ok_or_else(|| anyhow!("Copies won from card {} overflow u128", self.card)))
+    }
+}
+
+fn parse_card(input: &str) -> IResult<&str, Card> {
+    let (input, (id, mut winning, mut owned)) = tuple((
+        tag("Card")
+            .precedes(space1)
+            .precedes(complete::u32)
+            .terminated(complete::char(':')),
+        parse_nums,
+        preceded(space0, complete::char('|')).precedes(parse_nums),
+    ))
+    .parse(input)?;
+
+    winning.sort_unstable();
+    winning.dedup();
+    owned.sort_unstable();
+
+    Ok((input, Card { id, winning, owned }))
+}
+
+fn parse_nums(input: &str) -> IResult<&str, Vec<u32>> {
+    many0(preceded(space1, complete::u32)).parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[rstest]
+    #[case("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", 1, 4)]
+    #[case("Card  10: 10 74 58 71 57 35 34 96 77 18 | 14 27 22 18 70 42 56 94 76 74 85 73 61 34 88 45 39 64 35 87 90 58 91 75 54", 10, 5)]
+    fn it_parses_cards(#[case] input: &str, #[case] id: u32, #[case] matches: usize) {
+        let card = Card::parse(input).unwrap();
+        assert_eq!(id, card.id);
+        assert_eq!(matches, card.matches());
+    }
+
+    #[rstest]
+    #[case("Card 1 41 | 41")]
+    #[case("Card 1: 41 41")]
+    #[case("Card x: 41 | 41")]
+    fn it_rejects_malformed_cards(#[case] input: &str) {
+        assert!(Card::parse(input).is_err());
+    }
+
+    #[test]
+    fn it_computes_matches_points_and_copies() {
+        let scratchcards = Scratchcards::parse(INPUT).unwrap();
+        assert_eq!(vec![4, 2, 2, 1, 0, 0], scratchcards.match_counts());
+        assert_eq!(13, scratchcards.total_points().unwrap());
+        assert_eq!(vec![1, 2, 4, 8, 14, 1], scratchcards.copies().unwrap());
+        assert_eq!(30, scratchcards.total_cards().unwrap());
+    }
+
+    #[test]
+    fn it_does_not_overflow_on_long_cascades() {
+        // every card wins a copy of the next 3 cards, so the copies grow like tribonacci numbers
+        let copies = cascade(vec![3; 100]).last().unwrap().unwrap();
+        assert!(copies > u64::MAX as u128);
+    }
+
+    #[test]
+    fn it_reports_overflowing_counts() {
+        // the tribonacci numbers pass u128::MAX within 150 cards
+        let copies = cascade(vec![3; 1000]).collect::<Vec<_>>();
+        assert!(copies.len() < 150);
+        assert!(copies[..copies.len() - 1].iter().all(|c| c.is_ok()));
+        assert!(copies.last().unwrap().is_err());
+
+        let input = (1..=150)
+            .map(|id| format!("Card {}: 1 2 3 | 1 2 3", id))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(Scratchcards::parse(&input).unwrap().total_cards().is_err());
+
+        let numbers = (1..=129)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let card = Card::parse(&format!("Card 1: {} | {}", numbers, numbers)).unwrap();
+        assert!(card.points().is_err());
+        let card = Card::parse(&format!("Card 1: {} | {}", &numbers[2..], numbers)).unwrap();
+        assert_eq!(1 << 127, card.points().unwrap());
+    }
+
+    #[test]
+    fn it_streams_cards() {
+        let total = cascade(stream_cards(INPUT).map(|card| card.unwrap().matches()))
+            .sum::<Result<u128>>()
+            .unwrap();
+        assert_eq!(30, total);
+    }
+}
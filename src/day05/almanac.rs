@@ -0,0 +1,492 @@
+use std::ops::Range;
+
+use anyhow::{anyhow, bail, Result};
+use nom::{
+    character::complete::{self, alpha1, line_ending, multispace0, multispace1, space1},
+    combinator::all_consuming,
+    multi::{many1, separated_list1},
+    sequence::{separated_pair, terminated, tuple},
+    IResult, Parser,
+};
+use nom_supreme::{tag::complete::tag, ParserExt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapRange {
+    pub destination: u64,
+    pub source: u64,
+    pub length: u64,
+}
+
+impl MapRange {
+    // both ranges have to end within u64, as every function is defined over 0..u64::MAX
+    pub fn new(destination: u64, source: u64, length: u64) -> Result<Self> {
+        if source.checked_add(length).is_none() || destination.checked_add(length).is_none() {
+            bail!(
+                "Range {} {} {} goes beyond {}",
+                destination,
+                source,
+                length,
+                u64::MAX
+            );
+        }
+
+        Ok(Self {
+            destination,
+            source,
+            length,
+        })
+    }
+
+    // ranges built by hand rather than parsed are cut at u64::MAX
+    pub fn source_range(&self) -> Range<u64> {
+        self.source..self.source.saturating_add(self.length)
+    }
+
+    pub fn destination_range(&self) -> Range<u64> {
+        self.destination..self.destination.saturating_add(self.length)
+    }
+}
+
+// one `<source>-to-<destination> map:` block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryMap<'a> {
+    pub source: &'a str,
+    pub destination: &'a str,
+    pub ranges: Vec<MapRange>,
+}
+
+impl CategoryMap<'_> {
+    pub fn name(&self) -> String {
+        format!("{}-to-{}", self.source, self.destination)
+    }
+
+    // numbers outside of every source range map to themselves,
+    // and the first matching range wins if source ranges overlap
+    pub fn to_function(&self) -> PiecewiseLinear {
+        let mut breakpoints = vec![0, u64::MAX];
+        for range in self.ranges.iter() {
+            breakpoints.push(range.source);
+            breakpoints.push(range.source_range().end);
+        }
+        breakpoints.sort_unstable();
+        breakpoints.dedup();
+
+        let segments = breakpoints
+            .windows(2)
+            .map(|w| {
+                let offset = self
+                    .ranges
+                    .iter()
+                    .find(|range| range.source_range().contains(&w[0]))
+                    .map_or(0, |range| range.destination as i128 - range.source as i128);
+                Segment {
+                    domain: w[0]..w[1],
+                    offset,
+                }
+            })
+            .collect();
+
+        PiecewiseLinear::from_segments(segments)
+    }
+}
+
+// x -> x + offset for every x in `domain`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub domain: Range<u64>,
+    pub offset: i128,
+}
+
+impl Segment {
+    fn apply(&self, x: u64) -> u64 {
+        saturate(x as i128 + self.offset)
+    }
+}
+
+// outputs beyond u64 only come from map ranges built by hand, and stick to the bounds
+fn saturate(x: i128) -> u64 {
+    x.clamp(0, u64::MAX as i128) as u64
+}
+
+// A function over 0..u64::MAX made of sorted, gapless segments with slope 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiecewiseLinear {
+    segments: Vec<Segment>,
+}
+
+impl PiecewiseLinear {
+    pub fn identity() -> Self {
+        Self {
+            segments: vec![Segment {
+                domain: 0..u64::MAX,
+                offset: 0,
+            }],
+        }
+    }
+
+    fn from_segments(segments: Vec<Segment>) -> Self {
+        let mut merged: Vec<Segment> = Vec::with_capacity(segments.len());
+        for segment in segments.into_iter().filter(|s| !s.domain.is_empty()) {
+            match merged.last_mut() {
+                Some(last)
+                    if last.offset == segment.offset && last.domain.end == segment.domain.start =>
+                {
+                    last.domain.end = segment.domain.end;
+                }
+                _ => merged.push(segment),
+            }
+        }
+
+        Self { segments: merged }
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    // the first input of every segment
+    pub fn breakpoints(&self) -> Vec<u64> {
+        self.segments.iter().map(|s| s.domain.start).collect()
+    }
+
+    pub fn apply(&self, x: u64) -> u64 {
+        let idx = self.segments.partition_point(|s| s.domain.end <= x);
+        self.segments.get(idx).map_or(x, |segment| segment.apply(x))
+    }
+
+    // every input which maps to `y`, in ascending order
+    pub fn preimage(&self, y: u64) -> Vec<u64> {
+        self.segments
+            .iter()
+            .filter_map(|segment| {
+                let x = y as i128 - segment.offset;
+                let domain = segment.domain.start as i128..segment.domain.end as i128;
+                domain.contains(&x).then_some(x as u64)
+            })
+            .collect()
+    }
+
+    // `self` first, then `next`
+    pub fn then(&self, next: &PiecewiseLinear) -> PiecewiseLinear {
+        let mut segments = Vec::new();
+        for segment in self.segments.iter() {
+            let start = segment.apply(segment.domain.start);
+            let end = start.saturating_add(segment.domain.end - segment.domain.start);
+
+            let first = next.segments.partition_point(|s| s.domain.end <= start);
+            for next_segment in next.segments[first..]
+                .iter()
+                .take_while(|s| s.domain.start < end)
+            {
+                let overlap_start = start.max(next_segment.domain.start);
+                let overlap_end = end.min(next_segment.domain.end);
+                segments.push(Segment {
+                    domain: saturate(overlap_start as i128 - segment.offset)
+                        ..saturate(overlap_end as i128 - segment.offset),
+                    offset: segment.offset + next_segment.offset,
+                });
+            }
+        }
+
+        PiecewiseLinear::from_segments(segments)
+    }
+
+    // As every segment is increasing, the lowest output over a range is at the start of
+    // its intersection with some segment, so only the breakpoints need to be visited.
+    pub fn lowest_output(&self, ranges: &[Range<u64>]) -> Option<u64> {
+        ranges
+            .iter()
+            .filter(|range| !range.is_empty())
+            .flat_map(|range| {
+                let first = self
+                    .segments
+                    .partition_point(|s| s.domain.end <= range.start);
+                self.segments[first..]
+                    .iter()
+                    .take_while(|s| s.domain.start < range.end)
+                    .map(|s| s.apply(s.domain.start.max(range.start)))
+            })
+            .min()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Almanac<'a> {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<CategoryMap<'a>>,
+}
+
+impl<'a> Almanac<'a> {
+    pub fn parse(input: &'a str) -> Result<Self> {
+        all_consuming(terminated(parse_almanac, multispace0))
+            .parse(input.trim_start())
+            .map(|(_, almanac)| almanac)
+            .map_err(|e| anyhow!("Failed to parse almanac: {}", e))
+    }
+
+    // category names along the chain of maps, e.g. seed, soil, ..., location
+    pub fn categories(&self) -> Vec<&'a str> {
+        let mut categories = Vec::with_capacity(self.maps.len() + 1);
+        if let Some(first) = self.maps.first() {
+            categories.push(first.source);
+        }
+        categories.extend(self.maps.iter().map(|map| map.destination));
+        categories
+    }
+
+    pub fn map(&self, source: &str) -> Option<&CategoryMap<'a>> {
+        self.maps.iter().find(|map| map.source == source)
+    }
+
+    // every layer composed into a single seed -> location function
+    pub fn compose(&self) -> PiecewiseLinear {
+        self.maps
+            .iter()
+            .fold(PiecewiseLinear::identity(), |function, map| {
+                function.then(&map.to_function())
+            })
+    }
+
+    // the seeds line read as `start length` pairs
+    pub fn seed_ranges(&self) -> Result<Vec<Range<u64>>> {
+        self.seeds
+            .chunks_exact(2)
+            .map(|pair| match pair[0].checked_add(pair[1]) {
+                Some(end) => Ok(pair[0]..end),
+                None => bail!(
+                    "Seed range {} {} goes beyond {}",
+                    pair[0],
+                    pair[1],
+                    u64::MAX
+                ),
+            })
+            .collect()
+    }
+
+    pub fn lowest_location(&self, seed_ranges: &[Range<u64>]) -> Option<u64> {
+        self.compose().lowest_output(seed_ranges)
+    }
+}
+
+fn parse_almanac(input: &str) -> IResult<&str, Almanac<'_>> {
+    separated_pair(
+        tag("seeds:").precedes(many1(space1.precedes(complete::u64))),
+        multispace1,
+        separated_list1(multispace1, parse_category_map),
+    )
+    .map(|(seeds, maps)| Almanac { seeds, maps })
+    .parse(input)
+}
+
+fn parse_category_map(input: &str) -> IResult<&str, CategoryMap<'_>> {
+    let (input, (source, destination)) = separated_pair(alpha1, tag("-to-"), alpha1)
+        .terminated(tag(" map:"))
+        .parse(input)?;
+    let (input, ranges) = many1(
+        line_ending.precedes(
+            tuple((
+                complete::u64,
+                space1.precedes(complete::u64),
+                space1.precedes(complete::u64),
+            ))
+            .map_res(|(destination, source, length)| MapRange::new(destination, source, length)),
+        ),
+    )
+    .parse(input)?;
+
+    Ok((
+        input,
+        CategoryMap {
+            source,
+            destination,
+            ranges,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn it_parses_categories() {
+        let almanac = Almanac::parse(INPUT).unwrap();
+        assert_eq!(vec![79, 14, 55, 13], almanac.seeds);
+        assert_eq!(
+            vec![
+                "seed",
+                "soil",
+                "fertilizer",
+                "water",
+                "light",
+                "temperature",
+                "humidity",
+                "location"
+            ],
+            almanac.categories()
+        );
+        assert_eq!("seed-to-soil", almanac.maps[0].name());
+        assert_eq!(4, almanac.map("fertilizer").unwrap().ranges.len());
+    }
+
+    #[test]
+    fn it_rejects_malformed_almanacs() {
+        assert!(Almanac::parse("seeds: 1 2\n\nseed-to-soil map:\n1 2").is_err());
+        assert!(Almanac::parse("seeds: 1 2\n\nseed-soil map:\n1 2 3").is_err());
+    }
+
+    #[rstest]
+    #[case("0 18446744073709551615 1")]
+    #[case("18446744073709551615 0 1")]
+    #[case("0 18446744073709551000 1000")]
+    fn it_rejects_ranges_beyond_u64(#[case] range: &str) {
+        let input = format!("seeds: 1 2\n\nseed-to-soil map:\n{}", range);
+        assert!(Almanac::parse(&input).is_err());
+    }
+
+    #[test]
+    fn it_accepts_ranges_ending_at_u64_max() {
+        let almanac =
+            Almanac::parse("seeds: 1 2\n\nseed-to-soil map:\n0 18446744073709551614 1").unwrap();
+        assert_eq!(0, almanac.compose().apply(u64::MAX - 1));
+    }
+
+    #[test]
+    fn it_rejects_seed_ranges_beyond_u64() {
+        let almanac =
+            Almanac::parse("seeds: 18446744073709551615 1\n\nseed-to-soil map:\n1 2 3").unwrap();
+        assert!(almanac.seed_ranges().is_err());
+    }
+
+    #[test]
+    fn it_saturates_hand_built_ranges() {
+        let map = CategoryMap {
+            source: "seed",
+            destination: "soil",
+            ranges: vec![MapRange {
+                destination: u64::MAX - 1,
+                source: u64::MAX - 10,
+                length: 100,
+            }],
+        };
+        let function = map.to_function();
+        assert_eq!(vec![0, u64::MAX - 10], function.breakpoints());
+        assert_eq!(u64::MAX - 1, function.apply(u64::MAX - 10));
+        assert_eq!(u64::MAX, function.apply(u64::MAX - 5));
+        assert_eq!(
+            vec![0, u64::MAX - 10],
+            PiecewiseLinear::identity().then(&function).breakpoints()
+        );
+    }
+
+    #[rstest]
+    #[case(79, 82)]
+    #[case(14, 43)]
+    #[case(55, 86)]
+    #[case(13, 35)]
+    fn it_composes_all_layers(#[case] seed: u64, #[case] location: u64) {
+        let almanac = Almanac::parse(INPUT).unwrap();
+        assert_eq!(location, almanac.compose().apply(seed));
+    }
+
+    #[test]
+    fn it_matches_layer_by_layer_lookup() {
+        let almanac = Almanac::parse(INPUT).unwrap();
+        let composed = almanac.compose();
+        let layers = almanac
+            .maps
+            .iter()
+            .map(|map| map.to_function())
+            .collect::<Vec<_>>();
+
+        for seed in 0..200 {
+            let expected = layers.iter().fold(seed, |x, layer| layer.apply(x));
+            assert_eq!(expected, composed.apply(seed));
+        }
+    }
+
+    #[rstest]
+    #[case(82)]
+    #[case(46)]
+    #[case(0)]
+    fn it_inverts_locations(#[case] location: u64) {
+        let composed = Almanac::parse(INPUT).unwrap().compose();
+        let seeds = composed.preimage(location);
+        assert!(!seeds.is_empty());
+        assert!(seeds.iter().all(|&seed| composed.apply(seed) == location));
+    }
+
+    #[test]
+    fn it_lists_breakpoints() {
+        let map = CategoryMap {
+            source: "seed",
+            destination: "soil",
+            ranges: vec![
+                MapRange {
+                    destination: 50,
+                    source: 98,
+                    length: 2,
+                },
+                MapRange {
+                    destination: 52,
+                    source: 50,
+                    length: 48,
+                },
+            ],
+        };
+        assert_eq!(vec![0, 50, 98, 100], map.to_function().breakpoints());
+    }
+
+    #[test]
+    fn it_finds_the_lowest_location() {
+        let almanac = Almanac::parse(INPUT).unwrap();
+        assert_eq!(
+            Some(46),
+            almanac.lowest_location(&almanac.seed_ranges().unwrap())
+        );
+
+        let single_seeds = almanac
+            .seeds
+            .iter()
+            .map(|&seed| seed..seed + 1)
+            .collect::<Vec<_>>();
+        assert_eq!(Some(35), almanac.lowest_location(&single_seeds));
+        assert_eq!(None, almanac.lowest_location(&[]));
+    }
+}
@@ -1,100 +1,24 @@
-use std::ops::Range;
-
 use anyhow::{anyhow, Result};
-use nom::{
-    bytes::complete::take_until,
-    character::complete::{self, line_ending, space1},
-    multi::{many1, separated_list1},
-    IResult, Parser,
+
+use super::{
+    almanac::Almanac,
+    validation::{validate, Warning},
 };
-use nom_supreme::{tag::complete::tag, ParserExt};
 
 pub fn process_data(input: &str) -> Result<u64> {
-    let (input, seeds) = parse_seeds(input).map_err(|e| anyhow!("Failed to parse seeds: {}", e))?;
-    let (_, maps) = parse_maps(input).map_err(|e| anyhow!("Failed to parse maps: {}", e))?;
-
-    let mut short_location = u64::MAX;
-    seeds.into_iter().for_each(|seed| {
-        short_location = find_location(&maps, seed).min(short_location);
-    });
-
-    Ok(short_location)
-}
-
-fn parse_seeds(input: &str) -> IResult<&str, Vec<Range<u64>>> {
-    let (input, seeds) = tag("seeds: ")
-        .precedes(separated_list1(space1, complete::u64))
-        .parse(input)?;
-
-    let seeds = seeds
-        .chunks_exact(2)
-        .map(|item| {
-            let start = item[0];
-            let len = item[1];
-            start..start + len
-        })
-        .collect::<Vec<Range<u64>>>();
-
-    Ok((input, seeds))
-}
-
-fn parse_maps(input: &str) -> IResult<&str, Vec<Vec<[Range<u64>; 2]>>> {
-    let (input, maps) = many1(
-        take_until("map:").precedes(tag("map:")).precedes(many1(
-            line_ending
-                .precedes(separated_list1(space1, complete::u64))
-                .map(|item| {
-                    let dest = item[0];
-                    let src = item[1];
-                    let len = item[2];
-                    let src_range = src..src + len;
-                    let dest_range = dest..dest + len;
-                    [src_range, dest_range]
-                }),
-        )),
-    )
-    .parse(input)?;
-
-    Ok((input, maps))
+    let almanac = Almanac::parse(input)?;
+    let (lowest_location, _) = solve(&almanac)?;
+    Ok(lowest_location)
 }
 
-fn find_location(maps: &[Vec<[Range<u64>; 2]>], seed_range: Range<u64>) -> u64 {
-    let mut seeds = vec![seed_range];
-    for map in maps {
-        let mut new_seeds = Vec::new();
-        while let Some(seed) = seeds.pop() {
-            let mut found = false;
-            for [src, dest] in map {
-                let os = seed.start.max(src.start);
-                let oe = seed.end.min(src.end);
-                if os < oe {
-                    let sidx = os - src.start;
-                    new_seeds.push(dest.start + sidx..dest.start + sidx + oe - os);
-                    if os > seed.start {
-                        seeds.push(seed.start..os);
-                    }
-                    if oe < seed.end {
-                        seeds.push(oe..seed.end);
-                    }
-                    found = true;
-                    break;
-                }
-            }
-
-            if !found {
-                new_seeds.push(seed);
-            }
-        }
-        seeds = new_seeds;
-    }
+// the lowest location, along with the warnings found by validating the almanac first
+pub fn solve<'a>(almanac: &'a Almanac<'a>) -> Result<(u64, Vec<Warning<'a>>)> {
+    let warnings = validate(almanac);
+    let lowest_location = almanac
+        .lowest_location(&almanac.seed_ranges()?)
+        .ok_or_else(|| anyhow!("No seeds to plant"))?;
 
-    let mut min_location = seeds[0].start;
-    seeds.into_iter().for_each(|seed| {
-        if seed.start < min_location {
-            min_location = seed.start;
-        }
-    });
-    min_location
+    Ok((lowest_location, warnings))
 }
 
 #[cfg(test)]
@@ -138,4 +62,32 @@ humidity-to-location map:
 56 93 4";
         assert_eq!(46, process_data(input).unwrap());
     }
+
+    #[test]
+    fn it_returns_warnings_with_the_result() {
+        let almanac = Almanac::parse(
+            "seeds: 10 5
+
+seed-to-soil map:
+100 0 10
+200 5 10
+
+water-to-light map:
+1 2 3",
+        )
+        .unwrap();
+        let (lowest_location, warnings) = solve(&almanac).unwrap();
+        assert_eq!(205, lowest_location);
+        assert_eq!(
+            vec![
+                "seed-to-soil: source ranges #0 and #1 overlap on 5..10",
+                "seed-to-soil: destination soil does not match the next source water",
+                "water-to-light: unreachable",
+            ],
+            warnings
+                .iter()
+                .map(|warning| warning.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
 }
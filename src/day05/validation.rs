@@ -0,0 +1,234 @@
+use std::{collections::HashSet, fmt::Display, ops::Range};
+
+use super::almanac::{Almanac, CategoryMap};
+
+// Every warning borrows the maps it is about from the parsed almanac.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning<'a> {
+    // two source ranges of one map share numbers, only the first one is ever used for them
+    OverlappingSources {
+        map: &'a CategoryMap<'a>,
+        first: usize,
+        second: usize,
+        overlap: Range<u64>,
+    },
+    // numbers between the source ranges of one map which fall back to identity
+    IdentityGap {
+        map: &'a CategoryMap<'a>,
+        gap: Range<u64>,
+    },
+    // the destination of a map is not the source of the map after it
+    BrokenChain {
+        map: &'a CategoryMap<'a>,
+        next: &'a CategoryMap<'a>,
+    },
+    // a map which cannot be reached by following the chain from the first source category
+    UnreachableMap {
+        map: &'a CategoryMap<'a>,
+    },
+}
+
+impl Display for Warning<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::OverlappingSources {
+                map,
+                first,
+                second,
+                overlap,
+            } => write!(
+                f,
+                "{}: source ranges #{} and #{} overlap on {:?}",
+                map.name(),
+                first,
+                second,
+                overlap
+            ),
+            Warning::IdentityGap { map, gap } => {
+                write!(
+                    f,
+                    "{}: {:?} is not covered and maps to itself",
+                    map.name(),
+                    gap
+                )
+            }
+            Warning::BrokenChain { map, next } => write!(
+                f,
+                "{}: destination {} does not match the next source {}",
+                map.name(),
+                map.destination,
+                next.source
+            ),
+            Warning::UnreachableMap { map } => write!(f, "{}: unreachable", map.name()),
+        }
+    }
+}
+
+pub fn validate<'a>(almanac: &'a Almanac<'a>) -> Vec<Warning<'a>> {
+    let mut warnings = Vec::new();
+
+    for map in almanac.maps.iter() {
+        warnings.extend(overlapping_sources(map));
+        warnings.extend(identity_gaps(map));
+    }
+
+    for pair in almanac.maps.windows(2) {
+        if pair[0].destination != pair[1].source {
+            warnings.push(Warning::BrokenChain {
+                map: &pair[0],
+                next: &pair[1],
+            });
+        }
+    }
+
+    let mut reachable = HashSet::new();
+    let mut category = almanac.maps.first().map(|map| map.source);
+    while let Some(map) = category.and_then(|source| almanac.map(source)) {
+        if !reachable.insert(map.name()) {
+            break;
+        }
+        category = Some(map.destination);
+    }
+    warnings.extend(
+        almanac
+            .maps
+            .iter()
+            .filter(|map| !reachable.contains(&map.name()))
+            .map(|map| Warning::UnreachableMap { map }),
+    );
+
+    warnings
+}
+
+fn overlapping_sources<'a>(map: &'a CategoryMap<'a>) -> Vec<Warning<'a>> {
+    let mut warnings = Vec::new();
+    for (first, a) in map.ranges.iter().enumerate() {
+        for (second, b) in map.ranges.iter().enumerate().skip(first + 1) {
+            let (a, b) = (a.source_range(), b.source_range());
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if !overlap.is_empty() {
+                warnings.push(Warning::OverlappingSources {
+                    map,
+                    first,
+                    second,
+                    overlap,
+                });
+            }
+        }
+    }
+
+    warnings
+}
+
+// Only the numbers between the lowest and the highest source range are checked:
+// the ones before the first range and after the last one map to themselves in
+// every map of the puzzle, so reporting them would flag each map of a valid almanac.
+fn identity_gaps<'a>(map: &'a CategoryMap<'a>) -> Vec<Warning<'a>> {
+    let mut sources = map
+        .ranges
+        .iter()
+        .map(|range| range.source_range())
+        .filter(|range| !range.is_empty())
+        .collect::<Vec<_>>();
+    sources.sort_by_key(|range| range.start);
+
+    let mut warnings = Vec::new();
+    let mut covered_until = match sources.first() {
+        Some(range) => range.end,
+        None => return warnings,
+    };
+    for range in sources.iter().skip(1) {
+        if range.start > covered_until {
+            warnings.push(Warning::IdentityGap {
+                map,
+                gap: covered_until..range.start,
+            });
+        }
+        covered_until = covered_until.max(range.end);
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_accepts_the_example() {
+        let almanac = Almanac::parse(
+            "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15",
+        )
+        .unwrap();
+        assert_eq!(Vec::<Warning>::new(), validate(&almanac));
+    }
+
+    #[test]
+    fn it_reports_overlaps_and_gaps() {
+        let almanac = Almanac::parse(
+            "seeds: 1 2
+
+seed-to-soil map:
+50 10 10
+80 15 10
+0 40 5",
+        )
+        .unwrap();
+        // 0..10 and 45.. are left to identity on purpose
+        assert_eq!(
+            vec![
+                Warning::OverlappingSources {
+                    map: &almanac.maps[0],
+                    first: 0,
+                    second: 1,
+                    overlap: 15..20,
+                },
+                Warning::IdentityGap {
+                    map: &almanac.maps[0],
+                    gap: 25..40,
+                },
+            ],
+            validate(&almanac)
+        );
+    }
+
+    #[test]
+    fn it_reports_broken_chains_and_unreachable_maps() {
+        let almanac = Almanac::parse(
+            "seeds: 1 2
+
+seed-to-soil map:
+1 2 3
+
+water-to-light map:
+1 2 3",
+        )
+        .unwrap();
+        let warnings = validate(&almanac);
+        assert_eq!(
+            vec![
+                Warning::BrokenChain {
+                    map: &almanac.maps[0],
+                    next: &almanac.maps[1],
+                },
+                Warning::UnreachableMap {
+                    map: &almanac.maps[1],
+                },
+            ],
+            warnings
+        );
+        assert_eq!(
+            "seed-to-soil: destination soil does not match the next source water",
+            warnings[0].to_string()
+        );
+    }
+}
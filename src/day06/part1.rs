@@ -1,26 +1,30 @@
 use anyhow::{anyhow, Result};
 use nom::{
-    character::complete::{self, newline, space1},
+    character::complete::{digit1, newline, space1},
+    combinator::map_res,
     multi::many1,
     sequence::preceded,
     IResult, Parser,
 };
 use nom_supreme::{tag::complete::tag, ParserExt};
+use num::BigUint;
 
-pub fn process_data(input: &str) -> Result<u32> {
+use super::race::ways_to_win;
+
+pub fn process_data(input: &str) -> Result<BigUint> {
     let (_, (times, distances)) =
         parse_times_and_distances(input).map_err(|e| anyhow!("Failed to parse input: {}", e))?;
 
     let margin_of_error = distances
         .into_iter()
         .zip(times)
-        .map(|(distance, time)| winning_ways_of_race(distance, time))
-        .product::<u32>();
+        .map(|(distance, time)| ways_to_win(&time, &distance))
+        .product::<BigUint>();
 
     Ok(margin_of_error)
 }
 
-fn parse_times_and_distances(input: &str) -> IResult<&str, (Vec<u32>, Vec<u32>)> {
+fn parse_times_and_distances(input: &str) -> IResult<&str, (Vec<BigUint>, Vec<BigUint>)> {
     let (input, times) = tag("Time:").precedes(parse_nums).parse(input)?;
     let (input, _) = newline(input)?;
     let (input, distances) = tag("Distance:").precedes(parse_nums).parse(input)?;
@@ -28,18 +32,8 @@ fn parse_times_and_distances(input: &str) -> IResult<&str, (Vec<u32>, Vec<u32>)>
     Ok((input, (times, distances)))
 }
 
-fn parse_nums(input: &str) -> IResult<&str, Vec<u32>> {
-    many1(preceded(space1, complete::u32)).parse(input)
-}
-
-fn winning_ways_of_race(distance: u32, time: u32) -> u32 {
-    (1..time).fold(0, |acc, hold| {
-        if hold * (time - hold) > distance {
-            acc + 1
-        } else {
-            acc
-        }
-    })
+fn parse_nums(input: &str) -> IResult<&str, Vec<BigUint>> {
+    many1(preceded(space1, map_res(digit1, str::parse::<BigUint>))).parse(input)
 }
 
 #[cfg(test)]
@@ -53,7 +47,20 @@ mod tests {
             "Time:      7  15   30
 Distance:  9  40  200",
         )?;
-        assert_eq!(288, result);
+        assert_eq!(BigUint::from(288u32), result);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_accepts_races_beyond_u64() -> Result<()> {
+        // a time of 2^70 against a record of 0: every hold but 0 and 2^70 wins
+        let result = process_data(
+            "Time:      7  1180591620717411303424
+Distance:  9  0",
+        )?;
+        let holds = BigUint::from(2u32).pow(70) - 1u32;
+        assert_eq!(holds * 4u32, result);
 
         Ok(())
     }
@@ -69,7 +76,13 @@ Distance:  9  40  200", [7u32, 15, 30], [9u32, 40, 200]
         #[case] expected_distances: [u32; 3],
     ) {
         let (_, (times, distances)) = parse_times_and_distances(input).unwrap();
-        assert_eq!(expected_times, times.as_slice());
-        assert_eq!(expected_distances, distances.as_slice());
+        assert_eq!(
+            expected_times.map(BigUint::from).as_slice(),
+            times.as_slice()
+        );
+        assert_eq!(
+            expected_distances.map(BigUint::from).as_slice(),
+            distances.as_slice()
+        );
     }
 }
@@ -6,17 +6,17 @@ use nom::{
     IResult, Parser,
 };
 use nom_supreme::{tag::complete::tag, ParserExt};
-use rayon::prelude::*;
+use num::BigUint;
 
-pub fn process_data(input: &str) -> Result<u64> {
+use super::race::ways_to_win;
+
+pub fn process_data(input: &str) -> Result<BigUint> {
     let (_, (time, distance)) =
         parse_time_and_distance(input).map_err(|e| anyhow!("Failed to parse input: {}", e))?;
 
-    let time = time.parse()?;
-    let distance = distance.parse()?;
-    let margin_of_error = winning_ways_of_race(distance, time);
-
-    Ok(margin_of_error)
+    let time = time.parse::<BigUint>()?;
+    let distance = distance.parse::<BigUint>()?;
+    Ok(ways_to_win(&time, &distance))
 }
 
 fn parse_time_and_distance(input: &str) -> IResult<&str, (String, String)> {
@@ -32,21 +32,6 @@ fn parse_nums(input: &str) -> IResult<&str, String> {
     Ok((input, digits.concat()))
 }
 
-fn winning_ways_of_race(distance: u64, time: u64) -> u64 {
-    let start = (distance as f64 / time as f64).ceil() as u64;
-    let end = time - start;
-    (start..=end)
-        .into_par_iter()
-        .fold_with(0, |acc, hold| {
-            if hold * (time - hold) > distance {
-                acc + 1
-            } else {
-                acc
-            }
-        })
-        .sum()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -58,7 +43,19 @@ mod tests {
             "Time:      7  15   30
 Distance:  9  40  200",
         )?;
-        assert_eq!(71503, result);
+        assert_eq!(BigUint::from(71503u32), result);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_accepts_races_beyond_u64() -> Result<()> {
+        // the digits join into a time of 2^70 and a record of 0
+        let result = process_data(
+            "Time:      11805916207 17411303424
+Distance:  0",
+        )?;
+        assert_eq!(BigUint::from(2u32).pow(70) - 1u32, result);
 
         Ok(())
     }
@@ -0,0 +1,98 @@
+use std::ops::RangeInclusive;
+
+use num::{BigUint, One, Zero};
+
+// Holding the button for `hold` ms beats the record when `hold * (time - hold) > distance`,
+// i.e. when `hold` lies strictly between the roots of `hold^2 - time * hold + distance`.
+// The roots are located with an exact integer square root and then nudged onto the
+// first winning hold, so a hold which exactly ties the record never counts as a win.
+pub fn winning_holds(time: &BigUint, distance: &BigUint) -> Option<RangeInclusive<BigUint>> {
+    let time_squared = time * time;
+    let four_distance = distance * 4u32;
+    if time_squared <= four_distance {
+        return None;
+    }
+
+    let root = (time_squared - four_distance).sqrt();
+    let mut first = (time - root) / 2u32;
+    while !beats(&first, time, distance) {
+        first += 1u32;
+        if &first * 2u32 > *time {
+            return None;
+        }
+    }
+    while !first.is_zero() && beats(&(&first - 1u32), time, distance) {
+        first -= 1u32;
+    }
+
+    // the distance is symmetric around time / 2
+    let last = time - &first;
+    (first <= last).then_some(first..=last)
+}
+
+pub fn ways_to_win(time: &BigUint, distance: &BigUint) -> BigUint {
+    winning_holds(time, distance).map_or_else(BigUint::zero, |holds| {
+        holds.end() - holds.start() + BigUint::one()
+    })
+}
+
+fn beats(hold: &BigUint, time: &BigUint, distance: &BigUint) -> bool {
+    hold <= time && hold * (time - hold) > *distance
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(7, 9, Some((2, 5)))]
+    #[case(15, 40, Some((4, 11)))]
+    #[case(30, 200, Some((11, 19)))]
+    #[case(71530, 940200, Some((14, 71516)))]
+    // holding for 2 ms only ties the record
+    #[case(4, 4, None)]
+    #[case(4, 5, None)]
+    #[case(0, 0, None)]
+    #[case(1, 0, None)]
+    #[case(2, 0, Some((1, 1)))]
+    fn it_finds_winning_holds(
+        #[case] time: u64,
+        #[case] distance: u64,
+        #[case] expected: Option<(u64, u64)>,
+    ) {
+        let holds = winning_holds(&BigUint::from(time), &BigUint::from(distance));
+        let expected = expected.map(|(a, b)| BigUint::from(a)..=BigUint::from(b));
+        assert_eq!(expected, holds);
+    }
+
+    #[test]
+    fn it_matches_brute_force() {
+        for time in 0u64..60 {
+            for distance in 0..(time * time / 4 + 2) {
+                let expected = (0..=time)
+                    .filter(|hold| hold * (time - hold) > distance)
+                    .count();
+                let ways = ways_to_win(&BigUint::from(time), &BigUint::from(distance));
+                assert_eq!(BigUint::from(expected), ways, "{} {}", time, distance);
+            }
+        }
+    }
+
+    #[test]
+    fn it_handles_numbers_beyond_u64() {
+        let time = BigUint::from(10u32).pow(30);
+        // exactly ties at time / 2 and at no other hold
+        let distance = &time * &time / 4u32;
+        assert_eq!(BigUint::zero(), ways_to_win(&time, &distance));
+
+        let distance = distance - 1u32;
+        assert_eq!(BigUint::one(), ways_to_win(&time, &distance));
+
+        let distance = BigUint::from(u128::MAX);
+        let holds = winning_holds(&time, &distance).unwrap();
+        assert!(beats(holds.start(), &time, &distance));
+        assert!(!beats(&(holds.start() - 1u32), &time, &distance));
+    }
+}
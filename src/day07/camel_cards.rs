@@ -0,0 +1,321 @@
+use std::{cmp::Ordering, fmt::Display};
+
+use anyhow::{anyhow, bail, Result};
+use nom::{
+    character::complete::{self, alphanumeric1, multispace0, space1},
+    combinator::all_consuming,
+    multi::many1,
+    sequence::{separated_pair, terminated},
+    IResult, Parser,
+};
+
+// how hands of the same type are ordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    // compare the cards one by one in the order they were dealt
+    DealtOrder,
+    // compare the strongest cards first, like in poker
+    Strongest,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    // from the weakest to the strongest card
+    pub card_order: Vec<char>,
+    // cards which act like whatever card makes the strongest hand type
+    pub wildcards: Vec<char>,
+    pub hand_size: usize,
+    pub tie_break: TieBreak,
+}
+
+impl Rules {
+    pub fn standard() -> Self {
+        Self {
+            card_order: "23456789TJQKA".chars().collect(),
+            wildcards: Vec::new(),
+            hand_size: 5,
+            tie_break: TieBreak::DealtOrder,
+        }
+    }
+
+    // J cards are jokers, and the weakest individual cards
+    pub fn jokers() -> Self {
+        Self {
+            card_order: "J23456789TQKA".chars().collect(),
+            wildcards: vec!['J'],
+            hand_size: 5,
+            tie_break: TieBreak::DealtOrder,
+        }
+    }
+
+    pub fn strength(&self, card: char) -> Option<usize> {
+        self.card_order.iter().position(|&c| c == card)
+    }
+
+    pub fn hand(&self, cards: &str, bid: u32) -> Result<Hand> {
+        let cards = cards.chars().collect::<Vec<_>>();
+        if cards.len() != self.hand_size {
+            bail!(
+                "Expected {} cards, found {} in {}",
+                self.hand_size,
+                cards.len(),
+                cards.iter().collect::<String>()
+            );
+        }
+
+        let mut strengths = cards
+            .iter()
+            .map(|&card| {
+                self.strength(card)
+                    .ok_or_else(|| anyhow!("Unknown card {}", card))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if self.tie_break == TieBreak::Strongest {
+            strengths.sort_unstable_by(|a, b| b.cmp(a));
+        }
+
+        Ok(Hand {
+            kind: self.classify(&cards),
+            strengths,
+            cards,
+            bid,
+        })
+    }
+
+    pub fn classify(&self, cards: &[char]) -> HandsType {
+        let mut frequency: Vec<(char, usize)> = Vec::new();
+        let mut wildcards = 0;
+        for &card in cards {
+            if self.wildcards.contains(&card) {
+                wildcards += 1;
+                continue;
+            }
+            match frequency.iter_mut().find(|(c, _)| *c == card) {
+                Some((_, count)) => *count += 1,
+                None => frequency.push((card, 1)),
+            }
+        }
+
+        let mut pattern = frequency
+            .into_iter()
+            .map(|(_, count)| count)
+            .collect::<Vec<_>>();
+        pattern.sort_unstable_by(|a, b| b.cmp(a));
+        // the wildcards always join the largest group
+        match pattern.first_mut() {
+            Some(largest) => *largest += wildcards,
+            None if wildcards > 0 => pattern.push(wildcards),
+            None => (),
+        }
+
+        HandsType { pattern }
+    }
+
+    // The card every wildcard should stand for to reach `classify(cards)`:
+    // the most frequent other card, the strongest one on ties,
+    // or the strongest card overall when the hand is nothing but wildcards.
+    pub fn best_substitution(&self, cards: &[char]) -> Option<char> {
+        if !cards.iter().any(|card| self.wildcards.contains(card)) {
+            return None;
+        }
+
+        let count = |card: char| cards.iter().filter(|&&c| c == card).count();
+        cards
+            .iter()
+            .copied()
+            .filter(|card| !self.wildcards.contains(card))
+            .max_by_key(|&card| (count(card), self.strength(card)))
+            .or_else(|| {
+                self.card_order
+                    .iter()
+                    .rev()
+                    .copied()
+                    .find(|card| !self.wildcards.contains(card))
+            })
+    }
+
+    pub fn parse_hands(&self, input: &str) -> Result<Vec<Hand>> {
+        let (_, hands) = all_consuming(parse_hands_and_bids)
+            .parse(input.trim())
+            .map_err(|e| anyhow!("parse error: {:?}", e))?;
+        hands
+            .into_iter()
+            .map(|(cards, bid)| self.hand(cards, bid))
+            .collect()
+    }
+
+    // hands from the weakest to the strongest, i.e. in rank order
+    pub fn ranked_hands(&self, input: &str) -> Result<Vec<Hand>> {
+        let mut hands = self.parse_hands(input)?;
+        hands.sort();
+        Ok(hands)
+    }
+
+    pub fn total_winnings(&self, input: &str) -> Result<u32> {
+        let total_winning = self
+            .ranked_hands(input)?
+            .into_iter()
+            .enumerate()
+            .fold(0, |acc, (idx, h)| acc + (idx as u32 + 1) * h.bid);
+
+        Ok(total_winning)
+    }
+}
+
+// The sizes of the groups of equal cards, largest first.
+// Comparing them lexicographically ranks five of a kind over four of a kind,
+// four of a kind over a full house, and so on, for any hand size.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HandsType {
+    pattern: Vec<usize>,
+}
+
+impl HandsType {
+    pub fn pattern(&self) -> &[usize] {
+        &self.pattern
+    }
+}
+
+impl Display for HandsType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.pattern.as_slice() {
+            [] => write!(f, "Empty"),
+            [3, 2, ..] => write!(f, "FullHouse"),
+            [2, 2, ..] => write!(f, "TwoPair"),
+            [2, ..] => write!(f, "OnePair"),
+            [1, ..] => write!(f, "HighCard"),
+            [n, ..] => {
+                let words = ["Three", "Four", "Five", "Six", "Seven", "Eight", "Nine"];
+                match words.get(n - 3) {
+                    Some(word) => write!(f, "{}OfKind", word),
+                    None => write!(f, "{}OfKind", n),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand {
+    pub cards: Vec<char>,
+    pub kind: HandsType,
+    pub bid: u32,
+    // card strengths in tie-break order
+    strengths: Vec<usize>,
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.kind
+            .cmp(&other.kind)
+            .then_with(|| self.strengths.cmp(&other.strengths))
+    }
+}
+
+fn parse_hands_and_bids(input: &str) -> IResult<&str, Vec<(&str, u32)>> {
+    many1(terminated(
+        separated_pair(alphanumeric1, space1, complete::u32),
+        multispace0,
+    ))
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[rstest]
+    #[case("AAAAA", "FiveOfKind")]
+    #[case("AA8AA", "FourOfKind")]
+    #[case("23332", "FullHouse")]
+    #[case("TTT98", "ThreeOfKind")]
+    #[case("23432", "TwoPair")]
+    #[case("A23A4", "OnePair")]
+    #[case("23456", "HighCard")]
+    fn it_classifies_standard_hands(#[case] cards: &str, #[case] expected: &str) {
+        let hand = Rules::standard().hand(cards, 0).unwrap();
+        assert_eq!(expected, hand.kind.to_string());
+    }
+
+    #[rstest]
+    #[case("JJJJJ", "FiveOfKind")]
+    #[case("KTJJT", "FourOfKind")]
+    #[case("2233J", "FullHouse")]
+    #[case("2345J", "OnePair")]
+    fn it_classifies_hands_with_jokers(#[case] cards: &str, #[case] expected: &str) {
+        let hand = Rules::jokers().hand(cards, 0).unwrap();
+        assert_eq!(expected, hand.kind.to_string());
+    }
+
+    #[rstest]
+    #[case("32T3K", None)]
+    #[case("KTJJT", Some('T'))]
+    #[case("QQQJA", Some('Q'))]
+    #[case("2233J", Some('3'))]
+    #[case("JJJJJ", Some('A'))]
+    fn it_finds_the_best_substitution(#[case] cards: &str, #[case] expected: Option<char>) {
+        let cards = cards.chars().collect::<Vec<_>>();
+        assert_eq!(expected, Rules::jokers().best_substitution(&cards));
+    }
+
+    #[test]
+    fn it_rejects_invalid_hands() {
+        assert!(Rules::standard().hand("AAAA", 0).is_err());
+        assert!(Rules::standard().hand("AAAAX", 0).is_err());
+        assert!(Rules::standard().parse_hands("32T3K").is_err());
+    }
+
+    #[test]
+    fn it_supports_other_hand_sizes() {
+        let rules = Rules {
+            hand_size: 3,
+            ..Rules::standard()
+        };
+        let mut hands = [
+            rules.hand("AKQ", 1).unwrap(),
+            rules.hand("222", 2).unwrap(),
+            rules.hand("33A", 3).unwrap(),
+        ];
+        hands.sort();
+        assert_eq!(
+            vec![1, 3, 2],
+            hands.iter().map(|h| h.bid).collect::<Vec<_>>()
+        );
+        assert_eq!("ThreeOfKind", hands[2].kind.to_string());
+    }
+
+    #[test]
+    fn it_breaks_ties_by_strongest_card() {
+        let rules = Rules {
+            tie_break: TieBreak::Strongest,
+            ..Rules::standard()
+        };
+        // dealt order would rank 2AKQ9 below 9AKQ2, both have the same cards
+        let a = rules.hand("2AKQ9", 1).unwrap();
+        let b = rules.hand("9AKQ2", 2).unwrap();
+        assert_eq!(Ordering::Equal, a.cmp(&b));
+
+        let c = rules.hand("TAKQ2", 3).unwrap();
+        assert_eq!(Ordering::Greater, c.cmp(&b));
+    }
+
+    #[test]
+    fn it_computes_total_winnings() {
+        assert_eq!(6440, Rules::standard().total_winnings(INPUT).unwrap());
+        assert_eq!(5905, Rules::jokers().total_winnings(INPUT).unwrap());
+    }
+}
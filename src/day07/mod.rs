@@ -1,2 +1,4 @@
+pub mod camel_cards;
 pub mod part1;
 pub mod part2;
+pub mod table;
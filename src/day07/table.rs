@@ -0,0 +1,154 @@
+use anyhow::Result;
+
+use super::camel_cards::{HandsType, Rules};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub rank: usize,
+    pub cards: String,
+    // the card every wildcard stands for, if the hand has any
+    pub substitution: Option<char>,
+    // the cards with every wildcard replaced by `substitution`
+    pub best_cards: String,
+    pub kind: HandsType,
+    pub bid: u32,
+    pub winnings: u64,
+}
+
+pub fn ranked_table(rules: &Rules, input: &str) -> Result<Vec<Row>> {
+    let rows = rules
+        .ranked_hands(input)?
+        .into_iter()
+        .enumerate()
+        .map(|(idx, hand)| {
+            let substitution = rules.best_substitution(&hand.cards);
+            let best_cards = hand
+                .cards
+                .iter()
+                .map(|card| match substitution {
+                    Some(sub) if rules.wildcards.contains(card) => sub,
+                    _ => *card,
+                })
+                .collect();
+
+            Row {
+                rank: idx + 1,
+                cards: hand.cards.iter().collect(),
+                substitution,
+                best_cards,
+                winnings: (idx as u64 + 1) * hand.bid as u64,
+                kind: hand.kind,
+                bid: hand.bid,
+            }
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+pub fn to_text(rows: &[Row]) -> String {
+    let width = rows
+        .iter()
+        .map(|row| row.cards.len())
+        .max()
+        .unwrap_or(0)
+        .max(5);
+    let mut output = format!(
+        "{:>5}  {:<width$}  {:<3}  {:<width$}  {:<11}  {:>6}  {:>10}\n",
+        "rank", "cards", "sub", "best", "type", "bid", "winnings"
+    );
+    for row in rows {
+        output.push_str(&format!(
+            "{:>5}  {:<width$}  {:<3}  {:<width$}  {:<11}  {:>6}  {:>10}\n",
+            row.rank,
+            row.cards,
+            // `-` when the hand has no wildcard
+            row.substitution.unwrap_or('-'),
+            row.best_cards,
+            row.kind.to_string(),
+            row.bid,
+            row.winnings
+        ));
+    }
+    output.push_str(&format!(
+        "total winnings: {}\n",
+        rows.iter().map(|row| row.winnings).sum::<u64>()
+    ));
+
+    output
+}
+
+pub fn to_csv(rows: &[Row]) -> String {
+    let mut output = String::from("rank,cards,substitution,best,type,bid,winnings\n");
+    for row in rows {
+        output.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            row.rank,
+            row.cards,
+            row.substitution.map(String::from).unwrap_or_default(),
+            row.best_cards,
+            row.kind,
+            row.bid,
+            row.winnings
+        ));
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[test]
+    fn it_ranks_hands_with_jokers() {
+        let rows = ranked_table(&Rules::jokers(), INPUT).unwrap();
+        let ranked = rows
+            .iter()
+            .map(|row| row.cards.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["32T3K", "KK677", "T55J5", "QQQJA", "KTJJT"], ranked);
+        assert_eq!(5905u64, rows.iter().map(|row| row.winnings).sum());
+
+        let row = &rows[4];
+        assert_eq!(Some('T'), row.substitution);
+        assert_eq!("KTTTT", row.best_cards);
+        assert_eq!("FourOfKind", row.kind.to_string());
+    }
+
+    #[test]
+    fn it_substitutes_to_the_same_hand_type() {
+        let rules = Rules::jokers();
+        let plain = Rules {
+            wildcards: Vec::new(),
+            ..Rules::jokers()
+        };
+        for row in ranked_table(&rules, INPUT).unwrap() {
+            let best_cards = row.best_cards.chars().collect::<Vec<_>>();
+            assert_eq!(row.kind, plain.classify(&best_cards));
+        }
+    }
+
+    #[test]
+    fn it_exports_text_and_csv() {
+        let rows = ranked_table(&Rules::jokers(), INPUT).unwrap();
+
+        let text = to_text(&rows);
+        assert!(text.starts_with(" rank  cards  sub  best   type"));
+        assert!(text.contains("    1  32T3K  -    32T3K  OnePair         765         765\n"));
+        assert!(text.contains("    5  KTJJT  T    KTTTT  FourOfKind      220        1100\n"));
+        assert!(text.ends_with("total winnings: 5905\n"));
+
+        let csv = to_csv(&rows);
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!("rank,cards,substitution,best,type,bid,winnings", lines[0]);
+        assert_eq!("1,32T3K,,32T3K,OnePair,765,765", lines[1]);
+        assert_eq!("5,KTJJT,T,KTTTT,FourOfKind,220,1100", lines[5]);
+    }
+}
@@ -0,0 +1,215 @@
+use anyhow::{bail, Result};
+use num::Integer;
+
+use super::network::Network;
+
+// The steps at which a ghost (or a group of ghosts) stands on an end node:
+// every step in `finite`, plus every step `t >= start` with `t % period` in `residues`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hits {
+    pub finite: Vec<u128>,
+    pub start: u128,
+    pub period: u128,
+    pub residues: Vec<u128>,
+}
+
+impl Hits {
+    pub fn of_ghost(network: &Network, start: usize, is_end: impl Fn(&str) -> bool) -> Self {
+        let orbit = network.orbit(start);
+        let hits = orbit.hits(|node| is_end(network.name(node)));
+
+        let period = orbit.period as u128;
+        Self {
+            finite: hits
+                .iter()
+                .filter(|&&step| step < orbit.prefix)
+                .map(|&step| step as u128)
+                .collect(),
+            start: orbit.prefix as u128,
+            period,
+            residues: hits
+                .iter()
+                .filter(|&&step| step >= orbit.prefix)
+                .map(|&step| step as u128 % period)
+                .collect(),
+        }
+    }
+
+    pub fn contains(&self, step: u128) -> bool {
+        self.finite.contains(&step)
+            || (step >= self.start && self.residues.contains(&(step % self.period)))
+    }
+
+    // the steps at which both `self` and `other` hit
+    pub fn intersect(&self, other: &Hits) -> Hits {
+        let mut finite = self
+            .finite
+            .iter()
+            .filter(|&&step| other.contains(step))
+            .chain(other.finite.iter().filter(|&&step| self.contains(step)))
+            .copied()
+            .collect::<Vec<_>>();
+        finite.sort_unstable();
+        finite.dedup();
+
+        let period = self.period.lcm(&other.period);
+        let mut residues = self
+            .residues
+            .iter()
+            .flat_map(|&a| {
+                other
+                    .residues
+                    .iter()
+                    .filter_map(move |&b| crt(a, self.period, b, other.period))
+            })
+            .collect::<Vec<_>>();
+        residues.sort_unstable();
+        residues.dedup();
+
+        Hits {
+            finite,
+            start: self.start.max(other.start),
+            period,
+            residues,
+        }
+    }
+
+    // the first step from `from` on at which it hits
+    pub fn first_from(&self, from: u128) -> Option<u128> {
+        let finite = self
+            .finite
+            .iter()
+            .copied()
+            .filter(|&step| step >= from)
+            .min();
+        let from = from.max(self.start);
+        let periodic = self
+            .residues
+            .iter()
+            .map(|&residue| from + (residue + self.period - from % self.period) % self.period)
+            .min();
+
+        finite.into_iter().chain(periodic).min()
+    }
+}
+
+// The first step at which every ghost starting on a node matching `is_start`
+// stands on a node matching `is_end` at the same time.
+pub fn solve(
+    network: &Network,
+    is_start: impl Fn(&str) -> bool,
+    is_end: impl Fn(&str) -> bool + Copy,
+) -> Result<u128> {
+    let hits = network
+        .nodes_where(is_start)
+        .map(|start| Hits::of_ghost(network, start, is_end))
+        .reduce(|acc, hits| acc.intersect(&hits));
+
+    match hits.and_then(|hits| hits.first_from(1)) {
+        Some(steps) => Ok(steps),
+        None => bail!("The ghosts never reach the end nodes at the same step"),
+    }
+}
+
+// x with x = a (mod m) and x = b (mod n), reduced mod lcm(m, n)
+fn crt(a: u128, m: u128, b: u128, n: u128) -> Option<u128> {
+    let (m, n) = (m as i128, n as i128);
+    let gcd = m.extended_gcd(&n);
+    let diff = b as i128 - a as i128;
+    if diff % gcd.gcd != 0 {
+        return None;
+    }
+
+    let n_gcd = n / gcd.gcd;
+    let k = (diff / gcd.gcd).mod_floor(&n_gcd) * gcd.x.mod_floor(&n_gcd) % n_gcd;
+    let x = (a as i128 + m * k).mod_floor(&(m * n_gcd));
+    Some(x as u128)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn ends_with(c: char) -> impl Fn(&str) -> bool + Copy {
+        move |node: &str| node.ends_with(c)
+    }
+
+    #[rstest]
+    #[case(2, 4, 3, 5, Some(18))]
+    #[case(1, 4, 3, 6, Some(9))]
+    #[case(0, 4, 1, 6, None)]
+    #[case(0, 1, 0, 1, Some(0))]
+    fn it_solves_congruences(
+        #[case] a: u128,
+        #[case] m: u128,
+        #[case] b: u128,
+        #[case] n: u128,
+        #[case] expected: Option<u128>,
+    ) {
+        assert_eq!(expected, crt(a, m, b, n));
+    }
+
+    #[test]
+    fn it_solves_the_example() {
+        let network = Network::parse(
+            "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)",
+        )
+        .unwrap();
+        assert_eq!(6, solve(&network, ends_with('A'), ends_with('Z')).unwrap());
+    }
+
+    #[test]
+    fn it_handles_offsets_and_several_hits_per_cycle() {
+        // ghost 1 hits at 3, 6, 9, 12, ...
+        // ghost 2 hits twice per cycle of 5 after 2 steps: 2, 3, 7, 8, 12, 13, ...
+        // the lcm of the first hits would answer 6
+        let network = Network::parse(
+            "L
+
+1A = (1B, 1B)
+1B = (1C, 1C)
+1C = (1Z, 1Z)
+1Z = (1B, 1B)
+2A = (2B, 2B)
+2B = (2Z, 2Z)
+2Z = (2Y, 2Y)
+2Y = (2C, 2C)
+2C = (2D, 2D)
+2D = (2E, 2E)
+2E = (2Z, 2Z)",
+        )
+        .unwrap();
+        let is_end = |node: &str| node.ends_with('Z') || node == "2Y";
+        assert_eq!(3, solve(&network, ends_with('A'), is_end).unwrap());
+
+        let is_end = |node: &str| node.ends_with('Z') || node == "2D";
+        assert_eq!(12, solve(&network, ends_with('A'), is_end).unwrap());
+    }
+
+    #[test]
+    fn it_reports_when_no_common_step_exists() {
+        // ghost 1 hits on odd steps, ghost 2 on even steps
+        let network = Network::parse(
+            "L
+
+1A = (1Z, 1Z)
+1Z = (1A, 1A)
+2A = (2B, 2B)
+2B = (2Z, 2Z)
+2Z = (2B, 2B)",
+        )
+        .unwrap();
+        assert!(solve(&network, ends_with('A'), ends_with('Z')).is_err());
+    }
+}
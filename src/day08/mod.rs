@@ -1,2 +1,4 @@
+pub mod ghosts;
+pub mod network;
 pub mod part1;
 pub mod part2;
@@ -0,0 +1,381 @@
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::{anyhow, bail, Result};
+use nom::{
+    character::complete::{self, alpha1, alphanumeric1, multispace0, multispace1, space0},
+    combinator::all_consuming,
+    multi::many1,
+    sequence::{delimited, separated_pair, terminated},
+    IResult, Parser,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+// the state of a walker: the node it stands on and the next instruction to follow
+pub type State = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network<'a> {
+    instructions: Vec<Direction>,
+    names: Vec<&'a str>,
+    index: HashMap<&'a str, usize>,
+    // [left, right] of every node
+    links: Vec<[usize; 2]>,
+}
+
+impl<'a> Network<'a> {
+    pub fn parse(input: &'a str) -> Result<Self> {
+        let (_, (guide, nodes)) = all_consuming(parse_network)
+            .parse(input.trim())
+            .map_err(|e| anyhow!("Failed to parse network: {}", e))?;
+
+        let instructions = guide
+            .chars()
+            .map(|c| match c {
+                'L' => Ok(Direction::Left),
+                'R' => Ok(Direction::Right),
+                _ => Err(anyhow!("Invalid instruction {}", c)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let names = nodes.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        let index = names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| (*name, idx))
+            .collect::<HashMap<_, _>>();
+        if index.len() != names.len() {
+            bail!("Duplicated node in network");
+        }
+
+        let links = nodes
+            .iter()
+            .map(|(name, (left, right))| {
+                let lookup = |target: &str| {
+                    index
+                        .get(target)
+                        .copied()
+                        .ok_or_else(|| anyhow!("Node {} links to unknown node {}", name, target))
+                };
+                Ok([lookup(left)?, lookup(right)?])
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            instructions,
+            names,
+            index,
+            links,
+        })
+    }
+
+    pub fn instructions(&self) -> &[Direction] {
+        &self.instructions
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn node(&self, name: &str) -> Option<usize> {
+        self.index.get(name).copied()
+    }
+
+    pub fn name(&self, node: usize) -> &'a str {
+        self.names[node]
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = usize> {
+        0..self.names.len()
+    }
+
+    pub fn nodes_where<'s>(
+        &'s self,
+        predicate: impl Fn(&str) -> bool + 's,
+    ) -> impl Iterator<Item = usize> + 's {
+        self.nodes()
+            .filter(move |&node| predicate(self.names[node]))
+    }
+
+    pub fn step(&self, (node, instruction): State) -> State {
+        let next = match self.instructions[instruction] {
+            Direction::Left => self.links[node][0],
+            Direction::Right => self.links[node][1],
+        };
+        (next, (instruction + 1) % self.instructions.len())
+    }
+
+    // Follows the instructions from `start` until a state repeats.
+    // There are only `nodes * instructions` states, so this always terminates.
+    pub fn orbit(&self, start: usize) -> Orbit {
+        let state_count = self.names.len() * self.instructions.len();
+        let mut first_seen = vec![usize::MAX; state_count];
+        let mut states = Vec::new();
+
+        let mut state = (start, 0);
+        loop {
+            let key = state.0 * self.instructions.len() + state.1;
+            if first_seen[key] != usize::MAX {
+                return Orbit {
+                    prefix: first_seen[key],
+                    period: states.len() - first_seen[key],
+                    states,
+                };
+            }
+            first_seen[key] = states.len();
+            states.push(state);
+            state = self.step(state);
+        }
+    }
+
+    // The number of steps (at least one) from `start` to a node matching `predicate`.
+    // Fails instead of walking forever when the orbit of `start` never gets there.
+    pub fn steps_to(&self, start: usize, predicate: impl Fn(&str) -> bool) -> Result<usize> {
+        let orbit = self.orbit(start);
+        match (1..=orbit.states.len()).find(|&step| {
+            let (node, _) = orbit.state_at(step as u128);
+            predicate(self.names[node])
+        }) {
+            Some(steps) => Ok(steps),
+            None => bail!(
+                "{} never reaches a matching node: it loops every {} steps after {} steps",
+                self.names[start],
+                orbit.period,
+                orbit.prefix
+            ),
+        }
+    }
+
+    // `steps_to` for every node at once, `None` for the nodes which never get there.
+    // Like `steps_to`, this starts each node on the first instruction: the distances
+    // from the other instructions are in `state_distances_to`.
+    pub fn distances_to(&self, predicate: impl Fn(&str) -> bool) -> Vec<Option<usize>> {
+        self.state_distances_to(predicate)
+            .into_iter()
+            .map(|distances| distances[0])
+            .collect()
+    }
+
+    // The distance from every state, as `[node][instruction]`.
+    // Every state has a single successor, so a breadth first search backwards from
+    // the states right before a matching node finds each distance once.
+    pub fn state_distances_to(&self, predicate: impl Fn(&str) -> bool) -> Vec<Vec<Option<usize>>> {
+        let width = self.instructions.len();
+        let state_count = self.names.len() * width;
+        let key = |(node, instruction): State| node * width + instruction;
+        let matches = self
+            .names
+            .iter()
+            .map(|name| predicate(name))
+            .collect::<Vec<_>>();
+
+        let mut predecessors = vec![Vec::new(); state_count];
+        let mut distances = vec![None; state_count];
+        let mut queue = VecDeque::new();
+        for node in self.nodes() {
+            for instruction in 0..width {
+                let state = (node, instruction);
+                let next = self.step(state);
+                if matches[next.0] {
+                    distances[key(state)] = Some(1);
+                    queue.push_back(key(state));
+                } else {
+                    predecessors[key(next)].push(key(state));
+                }
+            }
+        }
+
+        while let Some(state) = queue.pop_front() {
+            let distance = distances[state].map(|d| d + 1);
+            for &previous in &predecessors[state] {
+                if distances[previous].is_none() {
+                    distances[previous] = distance;
+                    queue.push_back(previous);
+                }
+            }
+        }
+
+        distances
+            .chunks(width)
+            .map(|chunk| chunk.to_vec())
+            .collect()
+    }
+
+    // nodes from which following the instructions never reaches a node matching `predicate`
+    pub fn dead_ends(&self, predicate: impl Fn(&str) -> bool) -> Vec<usize> {
+        self.distances_to(predicate)
+            .into_iter()
+            .enumerate()
+            .filter(|(_, distance)| distance.is_none())
+            .map(|(node, _)| node)
+            .collect()
+    }
+}
+
+// The states visited from a start, step by step: `states[prefix..]` repeats forever.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Orbit {
+    pub prefix: usize,
+    pub period: usize,
+    pub states: Vec<State>,
+}
+
+impl Orbit {
+    pub fn state_at(&self, step: u128) -> State {
+        let step = match usize::try_from(step) {
+            Ok(step) if step < self.states.len() => step,
+            _ => {
+                let offset = (step - self.prefix as u128) % self.period as u128;
+                self.prefix + offset as usize
+            }
+        };
+        self.states[step]
+    }
+
+    // steps within `states` which land on a node matching `predicate`
+    pub fn hits(&self, mut predicate: impl FnMut(usize) -> bool) -> Vec<usize> {
+        self.states
+            .iter()
+            .enumerate()
+            .filter(|(_, (node, _))| predicate(*node))
+            .map(|(step, _)| step)
+            .collect()
+    }
+}
+
+type Nodes<'a> = Vec<(&'a str, (&'a str, &'a str))>;
+
+fn parse_network(input: &str) -> IResult<&str, (&str, Nodes<'_>)> {
+    separated_pair(
+        alpha1,
+        multispace1,
+        many1(terminated(
+            separated_pair(
+                alphanumeric1,
+                delimited(space0, complete::char('='), space0),
+                delimited(
+                    complete::char('('),
+                    separated_pair(
+                        alphanumeric1,
+                        delimited(space0, complete::char(','), space0),
+                        alphanumeric1,
+                    ),
+                    complete::char(')'),
+                ),
+            ),
+            multispace0,
+        )),
+    )
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+    #[test]
+    fn it_parses_the_network() {
+        let network = Network::parse(INPUT).unwrap();
+        assert_eq!(3, network.len());
+        assert_eq!(
+            vec![Direction::Left, Direction::Left, Direction::Right],
+            network.instructions()
+        );
+        let aaa = network.node("AAA").unwrap();
+        let bbb = network.node("BBB").unwrap();
+        assert_eq!((bbb, 1), network.step((aaa, 0)));
+        assert_eq!("BBB", network.name(bbb));
+    }
+
+    #[test]
+    fn it_rejects_invalid_networks() {
+        assert!(Network::parse("LX\n\nAAA = (AAA, AAA)").is_err());
+        assert!(Network::parse("L\n\nAAA = (AAA, BBB)").is_err());
+        assert!(Network::parse("L\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)").is_err());
+        assert!(Network::parse("L\n\nAAA = AAA, AAA").is_err());
+    }
+
+    #[test]
+    fn it_finds_the_orbit() {
+        let network = Network::parse(INPUT).unwrap();
+        let orbit = network.orbit(network.node("AAA").unwrap());
+        let zzz = network.node("ZZZ").unwrap();
+
+        // AAA -> BBB -> AAA -> BBB -> AAA -> BBB -> ZZZ, then ZZZ forever
+        assert_eq!(6, orbit.prefix);
+        assert_eq!(3, orbit.period);
+        assert_eq!(vec![6, 7, 8], orbit.hits(|node| node == zzz));
+        assert_eq!((zzz, 1), orbit.state_at(1_000_000));
+    }
+
+    #[test]
+    fn it_counts_steps_to_a_node() {
+        let network = Network::parse(INPUT).unwrap();
+        let aaa = network.node("AAA").unwrap();
+        let zzz = network.node("ZZZ").unwrap();
+        assert_eq!(6, network.steps_to(aaa, |name| name == "ZZZ").unwrap());
+        assert_eq!(1, network.steps_to(zzz, |name| name == "ZZZ").unwrap());
+        assert_eq!(2, network.steps_to(aaa, |name| name == "AAA").unwrap());
+    }
+
+    #[test]
+    fn it_detects_infinite_loops() {
+        let network = Network::parse(INPUT).unwrap();
+        let zzz = network.node("ZZZ").unwrap();
+        let error = network.steps_to(zzz, |name| name == "AAA").unwrap_err();
+        assert_eq!(
+            "ZZZ never reaches a matching node: it loops every 3 steps after 0 steps",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn it_finds_distances_and_dead_ends() {
+        let network = Network::parse(
+            "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)",
+        )
+        .unwrap();
+        let distances = network.distances_to(|name| name == "ZZZ");
+        for node in network.nodes() {
+            let steps = network.steps_to(node, |name| name == "ZZZ").ok();
+            assert_eq!(steps, distances[node], "{}", network.name(node));
+        }
+        assert_eq!(Some(2), distances[network.node("AAA").unwrap()]);
+        // CCC only leads to ZZZ when it is entered on the second instruction
+        let ccc = network.node("CCC").unwrap();
+        assert_eq!(None, distances[ccc]);
+        assert_eq!(
+            vec![None, Some(1)],
+            network.state_distances_to(|name| name == "ZZZ")[ccc]
+        );
+
+        let dead_ends = network
+            .dead_ends(|name| name == "ZZZ")
+            .into_iter()
+            .map(|node| network.name(node))
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["BBB", "CCC", "DDD", "EEE", "GGG"], dead_ends);
+    }
+}
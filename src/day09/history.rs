@@ -0,0 +1,157 @@
+use anyhow::{anyhow, bail, Result};
+use num::{BigInt, Zero};
+
+// The minimal-degree polynomial through a history, in Newton forward form:
+// `p(i) = sum of differences[k] * binomial(i, k)`, where `differences[k]` is the
+// first value of the k-th difference row and `i` is the index into the history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History {
+    len: usize,
+    differences: Vec<BigInt>,
+}
+
+impl History {
+    pub fn parse(line: &str) -> Result<Self> {
+        let values = line
+            .split_whitespace()
+            .map(|n| {
+                n.parse::<BigInt>()
+                    .map_err(|e| anyhow!("Invalid value {}: {}", n, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Self::fit(&values)
+    }
+
+    // Fails when the differences never become all zero, as then the history
+    // is too short to pin down its polynomial.
+    pub fn fit(values: &[BigInt]) -> Result<Self> {
+        let mut differences = Vec::new();
+        let mut row = values.to_vec();
+        while row.iter().any(|n| !n.is_zero()) {
+            if row.len() == 1 {
+                bail!(
+                    "The differences of {:?} never reach all zeros",
+                    values.iter().map(|n| n.to_string()).collect::<Vec<_>>()
+                );
+            }
+            differences.push(row[0].clone());
+            row = row.windows(2).map(|pair| &pair[1] - &pair[0]).collect();
+        }
+        if row.is_empty() {
+            bail!("Empty history");
+        }
+
+        Ok(Self {
+            len: values.len(),
+            differences,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // the zero polynomial and the constant ones both have degree 0
+    pub fn degree(&self) -> usize {
+        self.differences.len().saturating_sub(1)
+    }
+
+    // the value at `index`, which may lie before (negative) or after the history
+    pub fn at(&self, index: i128) -> BigInt {
+        let index = BigInt::from(index);
+        let mut binomial = BigInt::from(1);
+        let mut value = BigInt::zero();
+        for (k, difference) in self.differences.iter().enumerate() {
+            if k > 0 {
+                // binomial(i, k - 1) * (i - k + 1) is always a multiple of k
+                binomial = binomial * (&index - k + 1) / k;
+            }
+            value += difference * &binomial;
+        }
+
+        value
+    }
+
+    pub fn ahead(&self, steps: i128) -> BigInt {
+        self.at(self.len as i128 - 1 + steps)
+    }
+
+    pub fn behind(&self, steps: i128) -> BigInt {
+        self.at(-steps)
+    }
+}
+
+pub fn parse_histories(input: &str) -> Result<Vec<History>> {
+    input.lines().map(History::parse).collect()
+}
+
+pub fn sum(values: impl Iterator<Item = BigInt>) -> Result<i64> {
+    let total = values.sum::<BigInt>();
+    i64::try_from(&total).map_err(|_| anyhow!("Sum {} does not fit in an i64", total))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("0 3 6 9 12 15", 1, 18, -3)]
+    #[case("1 3 6 10 15 21", 2, 28, 0)]
+    #[case("10 13 16 21 30 45", 3, 68, 5)]
+    #[case("7 7 7", 0, 7, 7)]
+    #[case("0 0 0", 0, 0, 0)]
+    fn it_extrapolates_one_step(
+        #[case] line: &str,
+        #[case] degree: usize,
+        #[case] next: i64,
+        #[case] previous: i64,
+    ) {
+        let history = History::parse(line).unwrap();
+        assert_eq!(degree, history.degree());
+        assert_eq!(BigInt::from(next), history.ahead(1));
+        assert_eq!(BigInt::from(previous), history.behind(1));
+    }
+
+    #[test]
+    fn it_reproduces_the_history() {
+        let history = History::parse("10 13 16 21 30 45").unwrap();
+        let values = (0..6).map(|i| history.at(i)).collect::<Vec<_>>();
+        let expected = [10, 13, 16, 21, 30, 45].map(BigInt::from);
+        assert_eq!(expected.to_vec(), values);
+    }
+
+    #[test]
+    fn it_extrapolates_far_ahead_and_behind() {
+        // the triangular numbers (i + 1)(i + 2) / 2
+        let history = History::parse("1 3 6 10 15 21").unwrap();
+        let index = 1_000_005i128;
+        assert_eq!(
+            BigInt::from((index + 1) * (index + 2) / 2),
+            history.ahead(1_000_000)
+        );
+        assert_eq!(BigInt::from(0), history.behind(2));
+        assert_eq!(BigInt::from(1), history.behind(3));
+
+        // cubes overflow 64 bits long before the index does
+        let history = History::parse("0 1 8 27 64").unwrap();
+        assert_eq!(3, history.degree());
+        assert_eq!(BigInt::from(10u32).pow(24), history.at(100_000_000));
+        assert_eq!(-BigInt::from(10u32).pow(24), history.at(-100_000_000));
+    }
+
+    #[test]
+    fn it_flags_histories_without_zero_differences() {
+        assert!(History::parse("1 2 4 8").is_err());
+        assert!(History::parse("5").is_err());
+        assert!(History::parse("").is_err());
+        assert!(History::parse("1 x 3").is_err());
+        assert_eq!(0, History::parse("0").unwrap().degree());
+    }
+}
@@ -1,39 +1,11 @@
 use anyhow::Result;
 
-pub fn process_data(input: &str) -> Result<i32> {
-    let result = input
-        .lines()
-        .map(|l| {
-            l.split_whitespace()
-                .flat_map(|n| n.parse())
-                .collect::<Vec<i32>>()
-        })
-        .fold(0, |acc, n| acc + extrapolate(&n));
+use super::history::{parse_histories, sum};
 
-    Ok(result)
-}
-
-fn extrapolate(histories: &[i32]) -> i32 {
-    let mut diffs: Vec<Vec<i32>> = vec![histories.to_vec()];
-    calculate_diffs(histories, &mut diffs);
-
-    diffs
-        .iter()
-        .flat_map(|nums| nums.last())
-        .fold(0i32, |history, n| n + history)
-}
-
-fn calculate_diffs(histories: &[i32], diffs: &mut Vec<Vec<i32>>) {
-    let diff = histories
-        .iter()
-        .zip(&histories[1..])
-        .map(|(a, b)| b - a)
-        .collect::<Vec<i32>>();
-    diffs.push(diff.clone());
+pub fn process_data(input: &str) -> Result<i64> {
+    let histories = parse_histories(input)?;
 
-    if diff.iter().any(|&n| n != 0) {
-        calculate_diffs(&diff, diffs);
-    }
+    sum(histories.iter().map(|history| history.ahead(1)))
 }
 
 #[cfg(test)]
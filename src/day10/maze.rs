@@ -0,0 +1,359 @@
+use anyhow::{anyhow, bail, Result};
+
+// (row, column)
+pub type Position = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ];
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+
+    fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::South => (1, 0),
+            Direction::East => (0, 1),
+            Direction::West => (0, -1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Ground,
+    Pipe([Direction; 2]),
+}
+
+impl Tile {
+    pub fn from_char(c: char) -> Option<Tile> {
+        let pipe = |a, b| Some(Tile::Pipe([a, b]));
+        match c {
+            '.' => Some(Tile::Ground),
+            '|' => pipe(Direction::North, Direction::South),
+            '-' => pipe(Direction::East, Direction::West),
+            'L' => pipe(Direction::North, Direction::East),
+            'J' => pipe(Direction::North, Direction::West),
+            '7' => pipe(Direction::South, Direction::West),
+            'F' => pipe(Direction::South, Direction::East),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(&self) -> char {
+        match self {
+            Tile::Ground => '.',
+            Tile::Pipe(directions) => {
+                let has = |d| directions.contains(&d);
+                match (
+                    has(Direction::North),
+                    has(Direction::South),
+                    has(Direction::East),
+                ) {
+                    (true, true, _) => '|',
+                    (true, _, true) => 'L',
+                    (true, _, _) => 'J',
+                    (_, true, true) => 'F',
+                    (_, true, _) => '7',
+                    _ => '-',
+                }
+            }
+        }
+    }
+
+    pub fn connects(&self, direction: Direction) -> bool {
+        match self {
+            Tile::Ground => false,
+            Tile::Pipe(directions) => directions.contains(&direction),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipeMaze {
+    // the start tile is stored as the pipe it stands for
+    tiles: Vec<Vec<Tile>>,
+    start: Position,
+}
+
+impl PipeMaze {
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut start = None;
+        let mut tiles = Vec::new();
+        for (i, line) in input.trim().lines().enumerate() {
+            let mut row = Vec::new();
+            for (j, c) in line.trim().chars().enumerate() {
+                if c == 'S' {
+                    if start.is_some() {
+                        bail!("Found a second start tile at {:?}", (i, j));
+                    }
+                    start = Some((i, j));
+                    row.push(Tile::Ground);
+                    continue;
+                }
+                let tile = Tile::from_char(c)
+                    .ok_or_else(|| anyhow!("Invalid tile {} at {:?}", c, (i, j)))?;
+                row.push(tile);
+            }
+            tiles.push(row);
+        }
+
+        let start = start.ok_or_else(|| anyhow!("start not found"))?;
+        if tiles.iter().any(|row| row.len() != tiles[0].len()) {
+            bail!("The rows of the maze have different lengths");
+        }
+
+        let mut maze = Self { tiles, start };
+        let connections = Direction::ALL
+            .into_iter()
+            .filter(|&direction| match maze.neighbour(start, direction) {
+                Some(next) => maze.tile(next).connects(direction.opposite()),
+                None => false,
+            })
+            .collect::<Vec<_>>();
+        match connections.as_slice() {
+            &[a, b] => maze.tiles[start.0][start.1] = Tile::Pipe([a, b]),
+            _ => bail!(
+                "The start tile connects to {} pipes instead of 2",
+                connections.len()
+            ),
+        }
+
+        Ok(maze)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.tiles[0].len()
+    }
+
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    pub fn tile(&self, (i, j): Position) -> Tile {
+        self.tiles[i][j]
+    }
+
+    // the pipe hidden under the start tile, as one of `|-LJ7F`
+    pub fn start_shape(&self) -> char {
+        self.tile(self.start).to_char()
+    }
+
+    pub fn neighbour(&self, (i, j): Position, direction: Direction) -> Option<Position> {
+        let (di, dj) = direction.offset();
+        let i = i.checked_add_signed(di)?;
+        let j = j.checked_add_signed(dj)?;
+        (i < self.rows() && j < self.cols()).then_some((i, j))
+    }
+
+    // the neighbour towards `direction` when its pipe connects back
+    pub fn linked_neighbour(&self, position: Position, direction: Direction) -> Option<Position> {
+        self.neighbour(position, direction)
+            .filter(|&next| self.tile(next).connects(direction.opposite()))
+    }
+
+    // the tiles of the loop through the start, in walking order, starting with the start
+    pub fn main_loop(&self) -> Result<Vec<Position>> {
+        self.loop_from(self.start)
+    }
+
+    // the tiles of the loop through the pipe at `from`, in walking order
+    pub fn loop_from(&self, from: Position) -> Result<Vec<Position>> {
+        let mut path = vec![from];
+        let Tile::Pipe([mut direction, _]) = self.tile(from) else {
+            bail!("The tile at {:?} is not a pipe", from);
+        };
+
+        let mut current = from;
+        loop {
+            let next = self
+                .linked_neighbour(current, direction)
+                .ok_or_else(|| anyhow!("The loop breaks at {:?}", current))?;
+            if next == from {
+                return Ok(path);
+            }
+
+            let Tile::Pipe([a, b]) = self.tile(next) else {
+                unreachable!("only pipes connect");
+            };
+            direction = if a == direction.opposite() { b } else { a };
+            path.push(next);
+            current = next;
+        }
+    }
+
+    pub fn loop_mask(&self) -> Result<Vec<Vec<bool>>> {
+        let mut mask = vec![vec![false; self.cols()]; self.rows()];
+        for (i, j) in self.main_loop()? {
+            mask[i][j] = true;
+        }
+
+        Ok(mask)
+    }
+
+    // the loop tile the most steps away from the start, and that number of steps
+    pub fn farthest_point(&self) -> Result<(Position, usize)> {
+        let main_loop = self.main_loop()?;
+        let steps = main_loop.len() / 2;
+        Ok((main_loop[steps], steps))
+    }
+
+    // Tiles inside the main loop: walking along a row, every loop tile connected
+    // to the north crosses the loop, so inner tiles follow an odd number of them.
+    pub fn enclosed_mask(&self) -> Result<Vec<Vec<bool>>> {
+        let mask = self.loop_mask()?;
+        Ok(mask
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut inside = false;
+                row.iter()
+                    .enumerate()
+                    .map(|(j, &on_loop)| {
+                        if on_loop {
+                            inside ^= self.tiles[i][j].connects(Direction::North);
+                        }
+                        !on_loop && inside
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+
+    pub fn enclosed_tiles(&self) -> Result<Vec<Position>> {
+        let mask = self.enclosed_mask()?;
+        Ok((0..self.rows())
+            .flat_map(|i| (0..self.cols()).map(move |j| (i, j)))
+            .filter(|&(i, j)| mask[i][j])
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(".....\n.S-7.\n.|.|.\n.L-J.\n.....", 'F', 4)]
+    #[case("-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF", 'F', 4)]
+    #[case("..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...", 'F', 8)]
+    #[case("7-F7-\n.FJ|7\nSJLL7\n|F--J\nLJ.LJ", 'F', 8)]
+    #[case("F-7\n|.|\nL-S", 'J', 4)]
+    fn it_infers_the_start_and_the_farthest_point(
+        #[case] input: &str,
+        #[case] shape: char,
+        #[case] steps: usize,
+    ) {
+        let maze = PipeMaze::parse(input).unwrap();
+        assert_eq!(shape, maze.start_shape());
+        assert_eq!(steps, maze.farthest_point().unwrap().1);
+    }
+
+    #[test]
+    fn it_walks_the_main_loop_in_order() {
+        let maze = PipeMaze::parse("-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF").unwrap();
+        let main_loop = maze.main_loop().unwrap();
+        assert_eq!(
+            vec![
+                (1, 1),
+                (2, 1),
+                (3, 1),
+                (3, 2),
+                (3, 3),
+                (2, 3),
+                (1, 3),
+                (1, 2)
+            ],
+            main_loop
+        );
+        assert_eq!(((3, 3), 4), maze.farthest_point().unwrap());
+    }
+
+    #[rstest]
+    #[case(
+        "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........",
+        4
+    )]
+    #[case(
+        "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L",
+        10
+    )]
+    fn it_finds_enclosed_tiles(#[case] input: &str, #[case] expected: usize) {
+        let maze = PipeMaze::parse(input).unwrap();
+        assert_eq!(expected, maze.enclosed_tiles().unwrap().len());
+        assert_eq!(
+            expected,
+            maze.enclosed_mask()
+                .unwrap()
+                .iter()
+                .flatten()
+                .filter(|&&e| e)
+                .count()
+        );
+    }
+
+    #[rstest]
+    // no start
+    #[case(".F7\n.LJ")]
+    // two starts
+    #[case("SS\n..")]
+    // unknown tile
+    #[case("S-7\n|X|\nL-J")]
+    // ragged rows
+    #[case("S-7\n|.|\nL-J.")]
+    // the start connects to a single pipe
+    #[case("S-.\n...")]
+    // the start connects to three pipes
+    #[case(".|.\n-S-\n...")]
+    fn it_rejects_malformed_mazes(#[case] input: &str) {
+        assert!(PipeMaze::parse(input).is_err());
+    }
+
+    #[test]
+    fn it_reports_broken_loops() {
+        let maze = PipeMaze::parse("S-.\n|..").unwrap();
+        assert!(maze.main_loop().is_err());
+    }
+}
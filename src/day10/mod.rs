@@ -1,3 +1,6 @@
+pub mod maze;
 pub mod part1;
 pub mod part1_bfs; // breadth-first search
 pub mod part2;
+pub mod part2_flood; // flood fill of the maze upscaled 3x
+pub mod render;
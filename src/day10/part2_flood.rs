@@ -0,0 +1,273 @@
+use std::collections::VecDeque;
+
+use anyhow::Result;
+
+use super::maze::{Direction, PipeMaze, Position, Tile};
+
+// which pipes block the flood
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Walls {
+    // only the loop through the start
+    MainLoop,
+    // every closed loop of pipes, connected to the start or not
+    AllLoops,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regions {
+    pub loops: Vec<Vec<Position>>,
+    // tiles the flood from outside never reaches
+    pub enclosed: Vec<Position>,
+    // tiles the flood only reaches by squeezing between two pipes
+    pub squeeze_only: Vec<Position>,
+}
+
+pub fn process_data(input: &str) -> Result<u32> {
+    let maze = PipeMaze::parse(input)?;
+    let regions = flood(&maze, Walls::MainLoop)?;
+
+    Ok(regions.enclosed.len() as u32)
+}
+
+// Every tile becomes 3x3 cells, with the pipes drawn through the centre:
+//
+//   |  .#.    F  ...    -  ...
+//      .#.       .##       ###
+//      .#.       .#.       ...
+//
+// so the gap between two adjacent pipes is a row or column of open cells
+// and a flood of the open cells from the border squeezes through it.
+pub fn flood(maze: &PipeMaze, walls: Walls) -> Result<Regions> {
+    let loops = match walls {
+        Walls::MainLoop => vec![maze.main_loop()?],
+        Walls::AllLoops => closed_loops(maze),
+    };
+    let mut is_wall = vec![vec![false; maze.cols()]; maze.rows()];
+    for &(i, j) in loops.iter().flatten() {
+        is_wall[i][j] = true;
+    }
+
+    let (rows, cols) = (maze.rows() * 3, maze.cols() * 3);
+    let mut blocked = vec![vec![false; cols]; rows];
+    for (i, row) in is_wall.iter().enumerate() {
+        for (j, _) in row.iter().enumerate().filter(|(_, &wall)| wall) {
+            let (ci, cj) = (i * 3 + 1, j * 3 + 1);
+            blocked[ci][cj] = true;
+            if let Tile::Pipe(directions) = maze.tile((i, j)) {
+                for direction in directions {
+                    let (ai, aj) = match direction {
+                        Direction::North => (ci - 1, cj),
+                        Direction::South => (ci + 1, cj),
+                        Direction::East => (ci, cj + 1),
+                        Direction::West => (ci, cj - 1),
+                    };
+                    blocked[ai][aj] = true;
+                }
+            }
+        }
+    }
+
+    let border = (0..rows)
+        .flat_map(|i| [(i, 0), (i, cols - 1)])
+        .chain((0..cols).flat_map(|j| [(0, j), (rows - 1, j)]));
+    let outside = flood_fill(&blocked, border);
+
+    let tile_border = (0..maze.rows())
+        .flat_map(|i| [(i, 0), (i, maze.cols() - 1)])
+        .chain((0..maze.cols()).flat_map(|j| [(0, j), (maze.rows() - 1, j)]));
+    let outside_without_squeezing = flood_fill(&is_wall, tile_border);
+
+    let mut enclosed = Vec::new();
+    let mut squeeze_only = Vec::new();
+    for i in 0..maze.rows() {
+        for j in 0..maze.cols() {
+            if is_wall[i][j] {
+                continue;
+            }
+            if !outside[i * 3 + 1][j * 3 + 1] {
+                enclosed.push((i, j));
+            } else if !outside_without_squeezing[i][j] {
+                squeeze_only.push((i, j));
+            }
+        }
+    }
+
+    Ok(Regions {
+        loops,
+        enclosed,
+        squeeze_only,
+    })
+}
+
+// every loop of pipes, each starting from its first tile in reading order
+fn closed_loops(maze: &PipeMaze) -> Vec<Vec<Position>> {
+    let mut seen = vec![vec![false; maze.cols()]; maze.rows()];
+    let mut loops = Vec::new();
+    for i in 0..maze.rows() {
+        for j in 0..maze.cols() {
+            if seen[i][j] || maze.tile((i, j)) == Tile::Ground {
+                continue;
+            }
+            match maze.loop_from((i, j)) {
+                Ok(path) => {
+                    path.iter().for_each(|&(i, j)| seen[i][j] = true);
+                    loops.push(path);
+                }
+                Err(_) => seen[i][j] = true,
+            }
+        }
+    }
+
+    loops
+}
+
+// the cells reachable from `sources` without crossing a blocked cell
+fn flood_fill(blocked: &[Vec<bool>], sources: impl Iterator<Item = Position>) -> Vec<Vec<bool>> {
+    let (rows, cols) = (blocked.len(), blocked[0].len());
+    let mut reached = vec![vec![false; cols]; rows];
+    let mut queue = VecDeque::new();
+    for (i, j) in sources {
+        if !blocked[i][j] && !reached[i][j] {
+            reached[i][j] = true;
+            queue.push_back((i, j));
+        }
+    }
+
+    while let Some((i, j)) = queue.pop_front() {
+        let neighbours = [
+            (i.wrapping_sub(1), j),
+            (i + 1, j),
+            (i, j.wrapping_sub(1)),
+            (i, j + 1),
+        ];
+        for (ni, nj) in neighbours {
+            if ni < rows && nj < cols && !blocked[ni][nj] && !reached[ni][nj] {
+                reached[ni][nj] = true;
+                queue.push_back((ni, nj));
+            }
+        }
+    }
+
+    reached
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(
+        "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........",
+        4,
+        0
+    )]
+    #[case(
+        "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........",
+        4,
+        8
+    )]
+    #[case(
+        ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...",
+        8,
+        2
+    )]
+    #[case(
+        "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L",
+        10,
+        0
+    )]
+    fn it_floods_the_main_loop(
+        #[case] input: &str,
+        #[case] enclosed: usize,
+        #[case] squeeze_only: usize,
+    ) {
+        let maze = PipeMaze::parse(input).unwrap();
+        let regions = flood(&maze, Walls::MainLoop).unwrap();
+        assert_eq!(enclosed, regions.enclosed.len());
+        assert_eq!(squeeze_only, regions.squeeze_only.len());
+
+        // cross-check against the parity count
+        assert_eq!(maze.enclosed_tiles().unwrap(), regions.enclosed);
+        assert_eq!(enclosed as u32, process_data(input).unwrap());
+    }
+
+    #[test]
+    fn it_finds_the_squeezed_tiles() {
+        let maze = PipeMaze::parse(
+            "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........",
+        )
+        .unwrap();
+        let regions = flood(&maze, Walls::MainLoop).unwrap();
+        let squeezed = (3..5)
+            .flat_map(|i| (3..7).map(move |j| (i, j)))
+            .collect::<Vec<_>>();
+        assert_eq!(squeezed, regions.squeeze_only);
+    }
+
+    #[test]
+    fn it_handles_disconnected_loops() {
+        let maze = PipeMaze::parse(
+            "S-7F-7.
+|.||.|.
+L-JL-J.
+.......
+F7.F--7
+LJ.|F7|
+...LJLJ",
+        )
+        .unwrap();
+
+        let regions = flood(&maze, Walls::MainLoop).unwrap();
+        assert_eq!(1, regions.loops.len());
+        assert_eq!(vec![(1, 1)], regions.enclosed);
+
+        let regions = flood(&maze, Walls::AllLoops).unwrap();
+        assert_eq!(4, regions.loops.len());
+        assert_eq!(vec![(1, 1), (1, 4)], regions.enclosed);
+        assert_eq!(Vec::<Position>::new(), regions.squeeze_only);
+    }
+}
@@ -0,0 +1,215 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+
+use super::maze::{Direction, PipeMaze, Tile};
+
+const ENCLOSED_COLOUR: &str = "#f4a261";
+const OUTSIDE_COLOUR: &str = "#264653";
+const LOOP_COLOUR: &str = "#e9c46a";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Loop(Tile),
+    Enclosed,
+    Outside,
+}
+
+// Every tile as part of the loop, enclosed by it or outside of it,
+// using the same loop and parity as `PipeMaze::enclosed_tiles`.
+pub fn cells(maze: &PipeMaze) -> Result<Vec<Vec<Cell>>> {
+    let on_loop = maze.loop_mask()?;
+    let enclosed = maze.enclosed_mask()?;
+
+    Ok((0..maze.rows())
+        .map(|i| {
+            (0..maze.cols())
+                .map(|j| match (on_loop[i][j], enclosed[i][j]) {
+                    (true, _) => Cell::Loop(maze.tile((i, j))),
+                    (false, true) => Cell::Enclosed,
+                    (false, false) => Cell::Outside,
+                })
+                .collect()
+        })
+        .collect())
+}
+
+// The loop drawn with box-drawing characters, enclosed tiles as `█`
+// and outside tiles as blanks, or as coloured backgrounds with `ansi`.
+pub fn to_terminal(cells: &[Vec<Cell>], ansi: bool) -> String {
+    let mut output = String::new();
+    for row in cells {
+        for cell in row {
+            let glyph = match cell {
+                Cell::Loop(tile) => box_drawing(tile),
+                Cell::Enclosed if ansi => ' ',
+                Cell::Enclosed => '█',
+                Cell::Outside => ' ',
+            };
+            if ansi {
+                // 24-bit background colours, the loop on the outside colour
+                let background = match cell {
+                    Cell::Enclosed => ENCLOSED_COLOUR,
+                    _ => OUTSIDE_COLOUR,
+                };
+                let (r, g, b) = rgb(background);
+                write!(output, "\x1b[48;2;{};{};{}m{}", r, g, b, glyph).unwrap();
+            } else {
+                output.push(glyph);
+            }
+        }
+        if ansi {
+            output.push_str("\x1b[0m");
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+// Every tile is a `scale` x `scale` square, filled in the enclosed or the outside
+// colour, with the loop stroked through the middle of its tiles.
+pub fn to_svg(cells: &[Vec<Cell>], scale: usize) -> String {
+    let rows = cells.len();
+    let cols = cells.first().map_or(0, |row| row.len());
+    let half = scale as f64 / 2.0;
+
+    let mut output = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+        cols * scale,
+        rows * scale
+    );
+    writeln!(
+        output,
+        "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+        OUTSIDE_COLOUR
+    )
+    .unwrap();
+
+    let mut path = String::new();
+    for (i, row) in cells.iter().enumerate() {
+        for (j, cell) in row.iter().enumerate() {
+            let (x, y) = (j * scale, i * scale);
+            match cell {
+                Cell::Enclosed => writeln!(
+                    output,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                    x, y, scale, scale, ENCLOSED_COLOUR
+                )
+                .unwrap(),
+                Cell::Loop(Tile::Pipe(directions)) => {
+                    // a segment from the centre of the tile to each edge it connects
+                    let (cx, cy) = (x as f64 + half, y as f64 + half);
+                    for direction in directions {
+                        let (dx, dy) = match direction {
+                            Direction::North => (0.0, -half),
+                            Direction::South => (0.0, half),
+                            Direction::East => (half, 0.0),
+                            Direction::West => (-half, 0.0),
+                        };
+                        write!(path, "M{} {}l{} {}", cx, cy, dx, dy).unwrap();
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+    writeln!(
+        output,
+        "<path d=\"{}\" stroke=\"{}\" stroke-width=\"{}\" stroke-linecap=\"round\" fill=\"none\"/>",
+        path,
+        LOOP_COLOUR,
+        (scale as f64 / 3.0).max(1.0)
+    )
+    .unwrap();
+    output.push_str("</svg>\n");
+
+    output
+}
+
+pub fn render(input: &str) -> Result<String> {
+    let maze = PipeMaze::parse(input)?;
+    Ok(to_terminal(&cells(&maze)?, true))
+}
+
+pub fn render_svg(input: &str, scale: usize) -> Result<String> {
+    let maze = PipeMaze::parse(input)?;
+    Ok(to_svg(&cells(&maze)?, scale))
+}
+
+fn box_drawing(tile: &Tile) -> char {
+    match tile.to_char() {
+        '|' => '│',
+        '-' => '─',
+        'L' => '└',
+        'J' => '┘',
+        '7' => '┐',
+        'F' => '┌',
+        _ => ' ',
+    }
+}
+
+fn rgb(colour: &str) -> (u8, u8, u8) {
+    let channel = |at: usize| u8::from_str_radix(&colour[at..at + 2], 16).unwrap_or(0);
+    (channel(1), channel(3), channel(5))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+
+    #[test]
+    fn it_draws_the_loop_with_box_characters() {
+        let maze = PipeMaze::parse(INPUT).unwrap();
+        let text = to_terminal(&cells(&maze).unwrap(), false);
+        let lines = text.lines().map(str::trim_end).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "",
+                " ┌───────┐",
+                " │┌─────┐│",
+                " ││     ││",
+                " ││     ││",
+                " │└─┐ ┌─┘│",
+                " │██│ │██│",
+                " └──┘ └──┘",
+                "",
+            ],
+            lines
+        );
+    }
+
+    #[test]
+    fn it_colours_the_terminal_output() {
+        let maze = PipeMaze::parse(INPUT).unwrap();
+        let text = to_terminal(&cells(&maze).unwrap(), true);
+        assert_eq!(9, text.lines().count());
+        assert_eq!(4, text.matches("\x1b[48;2;244;162;97m ").count());
+        assert!(text.lines().all(|line| line.ends_with("\x1b[0m")));
+    }
+
+    #[test]
+    fn it_renders_svg() {
+        let svg = render_svg(INPUT, 10).unwrap();
+        assert!(svg
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"110\" height=\"90\">"));
+        assert_eq!(
+            4,
+            svg.matches(&format!("fill=\"{}\"/>", ENCLOSED_COLOUR))
+                .count()
+        );
+        // the start tile is an F: south and east from its centre
+        assert!(svg.contains("M15 15l0 5M15 15l5 0"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
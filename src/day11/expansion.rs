@@ -0,0 +1,170 @@
+use anyhow::{bail, Result};
+
+// (row, column)
+pub type Position = (usize, usize);
+
+// The galaxies as seen in the image, before the universe expands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub rows: usize,
+    pub cols: usize,
+    pub galaxies: Vec<Position>,
+}
+
+impl Image {
+    pub fn parse(input: &str) -> Result<Self> {
+        let lines = input.trim().lines().map(str::trim).collect::<Vec<_>>();
+        let cols = lines.first().map_or(0, |line| line.len());
+
+        let mut galaxies = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            if line.len() != cols {
+                bail!("Row {} has {} columns instead of {}", i, line.len(), cols);
+            }
+            for (j, c) in line.chars().enumerate() {
+                match c {
+                    '#' => galaxies.push((i, j)),
+                    '.' => (),
+                    _ => bail!("Invalid character {} at {:?}", c, (i, j)),
+                }
+            }
+        }
+
+        Ok(Self {
+            rows: lines.len(),
+            cols,
+            galaxies,
+        })
+    }
+
+    // the galaxies once every empty row has grown into `row_factor` rows
+    // and every empty column into `col_factor` columns
+    pub fn expand(&self, row_factor: u128, col_factor: u128) -> Vec<(u128, u128)> {
+        let rows = expand_axis(self.galaxies.iter().map(|&(i, _)| i), self.rows, row_factor);
+        let cols = expand_axis(self.galaxies.iter().map(|&(_, j)| j), self.cols, col_factor);
+
+        self.galaxies
+            .iter()
+            .map(|&(i, j)| (rows[i], cols[j]))
+            .collect()
+    }
+
+    // the sum of the Manhattan distances between every pair of galaxies
+    pub fn distance_sum(&self, row_factor: u128, col_factor: u128) -> u128 {
+        let (rows, cols): (Vec<_>, Vec<_>) =
+            self.expand(row_factor, col_factor).into_iter().unzip();
+
+        axis_distance_sum(rows) + axis_distance_sum(cols)
+    }
+}
+
+// The expanded coordinate of every line of an axis of `size` lines:
+// a prefix sum of the lines before it, each empty line counting `factor`.
+fn expand_axis(occupied: impl Iterator<Item = usize>, size: usize, factor: u128) -> Vec<u128> {
+    let mut is_empty = vec![true; size];
+    occupied.for_each(|idx| is_empty[idx] = false);
+
+    is_empty
+        .iter()
+        .scan(0, |next, &empty| {
+            let coordinate = *next;
+            *next += if empty { factor } else { 1 };
+            Some(coordinate)
+        })
+        .collect()
+}
+
+// With the coordinates sorted, the k-th one is the larger of its pair with
+// each of the k before it, so it adds `k * c` minus the sum of those k.
+fn axis_distance_sum(mut coordinates: Vec<u128>) -> u128 {
+    coordinates.sort_unstable();
+    coordinates
+        .iter()
+        .enumerate()
+        .fold((0, 0), |(total, prefix), (k, &c)| {
+            (total + k as u128 * c - prefix, prefix + c)
+        })
+        .0
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    fn brute_force(image: &Image, row_factor: u128, col_factor: u128) -> u128 {
+        let galaxies = image.expand(row_factor, col_factor);
+        let mut total = 0;
+        for (idx, a) in galaxies.iter().enumerate() {
+            for b in &galaxies[idx + 1..] {
+                total += a.0.abs_diff(b.0) + a.1.abs_diff(b.1);
+            }
+        }
+        total
+    }
+
+    #[rstest]
+    #[case(2, 2, 374)]
+    #[case(10, 10, 1030)]
+    #[case(100, 100, 8410)]
+    #[case(1, 1, 292)]
+    fn it_sums_the_distances(#[case] rows: u128, #[case] cols: u128, #[case] expected: u128) {
+        let image = Image::parse(INPUT).unwrap();
+        assert_eq!(expected, image.distance_sum(rows, cols));
+    }
+
+    #[rstest]
+    #[case(2, 1_000_000)]
+    #[case(7, 3)]
+    #[case(1, 0)]
+    fn it_matches_brute_force_per_axis(#[case] rows: u128, #[case] cols: u128) {
+        let image = Image::parse(INPUT).unwrap();
+        assert_eq!(
+            brute_force(&image, rows, cols),
+            image.distance_sum(rows, cols)
+        );
+    }
+
+    #[test]
+    fn it_expands_each_axis() {
+        let image = Image::parse(INPUT).unwrap();
+        let galaxies = image.expand(10, 1);
+        // rows 3 and 7 are empty
+        assert_eq!((0, 3), galaxies[0]);
+        assert_eq!((13, 6), galaxies[3]);
+        assert_eq!((27, 4), galaxies[8]);
+    }
+
+    #[test]
+    fn it_handles_images_without_empty_lines() {
+        let image = Image::parse("#.\n.#").unwrap();
+        assert_eq!(2, image.distance_sum(1_000_000, 1_000_000));
+        let image = Image::parse("#").unwrap();
+        assert_eq!(0, image.distance_sum(2, 2));
+    }
+
+    #[test]
+    fn it_supports_results_beyond_u64() {
+        let factor = 10u128.pow(19);
+        let image = Image::parse("#..\n...\n..#").unwrap();
+        assert_eq!(2 * factor + 2, image.distance_sum(factor, factor));
+    }
+
+    #[test]
+    fn it_rejects_invalid_images() {
+        assert!(Image::parse("#.\n.").is_err());
+        assert!(Image::parse("#x").is_err());
+    }
+}
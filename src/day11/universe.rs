@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Result};
+
+use super::expansion::Image;
+
+// The galaxies of an image after the universe expanded by the given factors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Universe {
+    galaxies: Vec<(u128, u128)>,
+}
+
+impl Universe {
+    pub fn new(image: &Image, row_factor: u128, col_factor: u128) -> Self {
+        Self {
+            galaxies: image.expand(row_factor, col_factor),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.galaxies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.galaxies.is_empty()
+    }
+
+    // galaxies are numbered in reading order of the image, from 0
+    pub fn galaxy(&self, idx: usize) -> (u128, u128) {
+        self.galaxies[idx]
+    }
+
+    pub fn distance(&self, a: usize, b: usize) -> u128 {
+        let (a, b) = (self.galaxies[a], self.galaxies[b]);
+        a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+    }
+
+    // the `k` galaxies closest to `idx` with their distances, closest first
+    pub fn nearest_neighbours(&self, idx: usize, k: usize) -> Vec<(usize, u128)> {
+        let mut neighbours = (0..self.len())
+            .filter(|&other| other != idx)
+            .map(|other| (other, self.distance(idx, other)))
+            .collect::<Vec<_>>();
+        neighbours.sort_by_key(|&(other, distance)| (distance, other));
+        neighbours.truncate(k);
+
+        neighbours
+    }
+
+    // the galaxy whose nearest neighbour is the farthest away, and that distance
+    pub fn most_isolated(&self) -> Option<(usize, u128)> {
+        (0..self.len())
+            .filter_map(|idx| {
+                self.nearest_neighbours(idx, 1)
+                    .first()
+                    .map(|&(_, distance)| (idx, distance))
+            })
+            .fold(None, |best, (idx, distance)| match best {
+                Some((_, farthest)) if farthest >= distance => best,
+                _ => Some((idx, distance)),
+            })
+    }
+
+    // every pair of galaxies counted in the bucket `distance / width * width`
+    pub fn histogram(&self, width: u128) -> Result<BTreeMap<u128, usize>> {
+        if width == 0 {
+            bail!("The buckets of a histogram cannot be empty");
+        }
+
+        let mut histogram = BTreeMap::new();
+        for a in 0..self.len() {
+            for b in a + 1..self.len() {
+                let bucket = self.distance(a, b) / width * width;
+                *histogram.entry(bucket).or_insert(0) += 1;
+            }
+        }
+
+        Ok(histogram)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    fn universe(factor: u128) -> Universe {
+        Universe::new(&Image::parse(INPUT).unwrap(), factor, factor)
+    }
+
+    #[rstest]
+    #[case(4, 8, 9)]
+    #[case(0, 6, 15)]
+    #[case(2, 5, 17)]
+    #[case(7, 8, 5)]
+    fn it_measures_pairs(#[case] a: usize, #[case] b: usize, #[case] expected: u128) {
+        let universe = universe(2);
+        assert_eq!(expected, universe.distance(a, b));
+        assert_eq!(expected, universe.distance(b, a));
+    }
+
+    #[test]
+    fn it_finds_nearest_neighbours() {
+        let universe = universe(2);
+        assert_eq!(
+            vec![(8, 5), (4, 6), (2, 9)],
+            universe.nearest_neighbours(7, 3)
+        );
+        assert_eq!(8, universe.nearest_neighbours(0, 100).len());
+        assert_eq!(Some((0, 6)), universe.most_isolated());
+    }
+
+    #[test]
+    fn it_builds_a_histogram() {
+        let universe = universe(2);
+        let histogram = universe.histogram(1).unwrap();
+        assert_eq!(36, histogram.values().sum::<usize>());
+        assert_eq!(Some(&4), histogram.get(&5));
+        let total = histogram
+            .iter()
+            .map(|(distance, &count)| distance * count as u128)
+            .sum::<u128>();
+        assert_eq!(374, total);
+
+        let histogram = universe.histogram(10).unwrap();
+        assert_eq!(vec![0, 10], histogram.keys().copied().collect::<Vec<_>>());
+        assert!(universe.histogram(0).is_err());
+    }
+
+    #[test]
+    fn it_takes_the_expansion_as_a_parameter() {
+        let image = Image::parse(INPUT).unwrap();
+        let universe = Universe::new(&image, 1_000_000, 1);
+        assert_eq!((2_000_007, 4), universe.galaxy(8));
+        assert_eq!(image.distance_sum(1_000_000, 1), {
+            let mut total = 0;
+            for a in 0..universe.len() {
+                for b in a + 1..universe.len() {
+                    total += universe.distance(a, b);
+                }
+            }
+            total
+        });
+    }
+}
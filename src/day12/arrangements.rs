@@ -0,0 +1,402 @@
+use std::ops::Add;
+
+use anyhow::{anyhow, bail, Result};
+use num::{Num, One, Zero};
+
+// A row of springs: `#` damaged, `.` operational, `?` unknown,
+// and the sizes of the contiguous groups of damaged springs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub springs: Vec<char>,
+    pub groups: Vec<usize>,
+}
+
+impl Record {
+    pub fn parse(line: &str) -> Result<Self> {
+        let (springs, groups) = line
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| anyhow!("failed to parse record: {}", line))?;
+
+        let springs = springs.chars().collect::<Vec<_>>();
+        if let Some(c) = springs.iter().find(|c| !"#.?".contains(**c)) {
+            bail!("Invalid spring {} in {}", c, line);
+        }
+        let groups = groups
+            .split(',')
+            .map(|n| {
+                n.parse()
+                    .map_err(|e| anyhow!("Invalid group {} in {}: {}", n, line, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if groups.contains(&0) {
+            bail!("Empty group in {}", line);
+        }
+
+        Ok(Self { springs, groups })
+    }
+
+    // `factor` copies of the springs joined by `?`, and `factor` copies of the groups
+    pub fn unfold(&self, factor: usize) -> Self {
+        let springs = vec![self.springs.clone(); factor].join(&'?');
+        Self {
+            springs,
+            groups: self.groups.repeat(factor),
+        }
+    }
+
+    pub fn matches(&self, arrangement: &str) -> bool {
+        let springs = arrangement.chars().collect::<Vec<_>>();
+        let groups = springs
+            .split(|&c| c == '.')
+            .filter(|group| !group.is_empty())
+            .map(|group| group.len())
+            .collect::<Vec<_>>();
+
+        springs.len() == self.springs.len()
+            && springs
+                .iter()
+                .zip(&self.springs)
+                .all(|(&c, &known)| known == '?' || c == known)
+            && groups == self.groups
+    }
+}
+
+// the arrangements of `record` unfolded `factor` times
+pub fn count_arrangements<T>(record: &Record, factor: usize) -> T
+where
+    T: Clone + Zero + One + Add<Output = T>,
+{
+    let record = record.unfold(factor);
+    let mut count = T::zero();
+    suffix_counts(&record.springs, &record.groups, |g, ways: &[T]| {
+        if g == 0 {
+            count = ways[0].clone();
+        }
+    });
+
+    count
+}
+
+// Bottom up over the groups from the last one: `ways[i]` counts the arrangements of
+// `springs[i..]` holding `groups[g..]`, and `visit(g, &ways)` sees the row of every `g`
+// from `groups.len()` down to 0. Only two rows are kept, so the memory is linear in
+// the springs unless `visit` keeps more.
+pub fn suffix_counts<T>(springs: &[char], groups: &[usize], mut visit: impl FnMut(usize, &[T]))
+where
+    T: Clone + Zero + One + Add<Output = T>,
+{
+    let n = springs.len();
+    let run = damaged_runs(springs);
+
+    // no groups left: only operational or unknown springs may remain
+    let mut next = vec![T::zero(); n + 1];
+    next[n] = T::one();
+    for i in (0..n).rev() {
+        next[i] = if springs[i] == '#' {
+            T::zero()
+        } else {
+            next[i + 1].clone()
+        };
+    }
+    visit(groups.len(), &next);
+
+    let mut ways = vec![T::zero(); n + 1];
+    for (g, &len) in groups.iter().enumerate().rev() {
+        ways[n] = T::zero();
+        for i in (0..n).rev() {
+            let mut count = if springs[i] == '#' {
+                T::zero()
+            } else {
+                ways[i + 1].clone()
+            };
+            if let Some(end) = group_end(springs, &run, i, len) {
+                count = count + next[end].clone();
+            }
+            ways[i] = count;
+        }
+        std::mem::swap(&mut ways, &mut next);
+        visit(g, &next);
+    }
+}
+
+// the number of springs from `i` on which could all be damaged
+pub fn damaged_runs(springs: &[char]) -> Vec<usize> {
+    let mut run = vec![0; springs.len() + 1];
+    for i in (0..springs.len()).rev() {
+        run[i] = if springs[i] == '.' { 0 } else { run[i + 1] + 1 };
+    }
+
+    run
+}
+
+// where the arrangement continues if a group of `len` springs starts at spring `i`
+pub fn group_end(springs: &[char], run: &[usize], i: usize, len: usize) -> Option<usize> {
+    let end = i + len;
+    (run[i] >= len && (end == springs.len() || springs[end] != '#'))
+        .then(|| (end + 1).min(springs.len()))
+}
+
+// `ways[g][i]` counts the arrangements of `springs[i..]` holding exactly `groups[g..]`,
+// every row of the `suffix_counts` table kept to walk it back. Counts are `u128` by
+// default, records unfolded many times need `BigUint`.
+// Arrangements are ordered lexicographically, so `#` comes before `.`.
+#[derive(Debug, Clone)]
+pub struct Arrangements<'a, T = u128> {
+    record: &'a Record,
+    run: Vec<usize>,
+    ways: Vec<Vec<T>>,
+}
+
+impl<'a, T> Arrangements<'a, T>
+where
+    T: Clone + Num + PartialOrd + From<u64>,
+{
+    pub fn new(record: &'a Record) -> Self {
+        let mut ways = vec![Vec::new(); record.groups.len() + 1];
+        suffix_counts(&record.springs, &record.groups, |g, row: &[T]| {
+            ways[g] = row.to_vec()
+        });
+
+        Self {
+            record,
+            run: damaged_runs(&record.springs),
+            ways,
+        }
+    }
+
+    pub fn count(&self) -> T {
+        self.ways[0][0].clone()
+    }
+
+    // the `k`-th arrangement, from 0
+    pub fn nth(&self, mut k: T) -> Option<String> {
+        if k >= self.ways[0][0] {
+            return None;
+        }
+
+        let mut arrangement = String::with_capacity(self.record.springs.len());
+        let (mut i, mut g) = (0, 0);
+        while i < self.record.springs.len() {
+            if let Some(next) = self.group_at(i, g) {
+                let count = &self.ways[g + 1][next];
+                if k < *count {
+                    arrangement.extend(vec!['#'; self.record.groups[g]]);
+                    if next > i + self.record.groups[g] {
+                        arrangement.push('.');
+                    }
+                    (i, g) = (next, g + 1);
+                    continue;
+                }
+                k = k - count.clone();
+            }
+
+            arrangement.push('.');
+            i += 1;
+        }
+
+        Some(arrangement)
+    }
+
+    // every arrangement in order, each one found from the table on demand
+    pub fn iter(&self) -> impl Iterator<Item = String> + '_ {
+        std::iter::successors(Some(T::zero()), |k| Some(k.clone() + T::one()))
+            .map_while(|k| self.nth(k))
+    }
+
+    pub fn random(&self, rng: &mut SplitMix64) -> Option<String> {
+        let count = self.count();
+        if count.is_zero() {
+            return None;
+        }
+        self.nth(rng.below(&count))
+    }
+
+    // where the arrangement continues if `groups[g]` starts at spring `i`
+    fn group_at(&self, i: usize, g: usize) -> Option<usize> {
+        let len = *self.record.groups.get(g)?;
+        group_end(&self.record.springs, &self.run, i, len)
+    }
+}
+
+// A small seeded generator, enough to pick arrangements uniformly.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in `0..bound`, from as many 64-bit draws as it takes to reach `bound`,
+    // rejecting the draws which would bias the modulo. `max` is the largest draw,
+    // which never overflows a `u128` bound.
+    pub fn below<T>(&mut self, bound: &T) -> T
+    where
+        T: Clone + Num + PartialOrd + From<u64>,
+    {
+        let base = T::from(u64::MAX) + T::one();
+        let mut words = 0;
+        let mut max = T::zero();
+        while max < *bound {
+            max = max * base.clone() + T::from(u64::MAX);
+            words += 1;
+        }
+
+        let zone = max.clone() - max % bound.clone();
+        loop {
+            let draw = (0..words).fold(T::zero(), |draw, _| {
+                draw * base.clone() + T::from(self.next_u64())
+            });
+            if draw < zone {
+                return draw % bound.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("???.### 1,1,3", 1, 1)]
+    #[case(".??..??...?##. 1,1,3", 4, 16384)]
+    #[case("?#?#?#?#?#?#?#? 1,3,1,6", 1, 1)]
+    #[case("????.#...#... 4,1,1", 1, 16)]
+    #[case("????.######..#####. 1,6,5", 4, 2500)]
+    #[case("?###???????? 3,2,1", 10, 506250)]
+    fn it_counts_arrangements(#[case] line: &str, #[case] folded: u128, #[case] unfolded: u128) {
+        let record = Record::parse(line).unwrap();
+        assert_eq!(folded, Arrangements::<u128>::new(&record).count());
+        assert_eq!(
+            unfolded,
+            Arrangements::<u128>::new(&record.unfold(5)).count()
+        );
+    }
+
+    #[test]
+    fn it_enumerates_in_order() {
+        let record = Record::parse(".??..??...?##. 1,1,3").unwrap();
+        let arrangements = Arrangements::<u128>::new(&record);
+        assert_eq!(
+            vec![
+                ".#...#....###.",
+                ".#....#...###.",
+                "..#..#....###.",
+                "..#...#...###.",
+            ],
+            arrangements.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(Some("..#..#....###.".to_string()), arrangements.nth(2));
+        assert_eq!(None, arrangements.nth(4));
+    }
+
+    #[rstest]
+    #[case("?###???????? 3,2,1")]
+    #[case("????.######..#####. 1,6,5")]
+    #[case("?#?#?#?#?#?#?#? 1,3,1,6")]
+    #[case("??????? 2,1")]
+    #[case("#.#? 1,1")]
+    fn it_lists_each_valid_arrangement_once(#[case] line: &str) {
+        let record = Record::parse(line).unwrap();
+        let arrangements = Arrangements::<u128>::new(&record);
+        let all = arrangements.iter().collect::<Vec<_>>();
+
+        assert_eq!(arrangements.count(), all.len() as u128);
+        assert!(all.iter().all(|arrangement| record.matches(arrangement)));
+        assert!(all.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn it_finds_the_kth_arrangement_of_huge_records() {
+        let record = Record::parse("?###???????? 3,2,1").unwrap().unfold(5);
+        let arrangements = Arrangements::<u128>::new(&record);
+        let last = arrangements.nth(arrangements.count() - 1).unwrap();
+        assert!(record.matches(&last));
+        assert!(last > arrangements.nth(0).unwrap());
+    }
+
+    #[test]
+    fn it_walks_records_beyond_u128() {
+        let record = Record::parse("?? 1").unwrap().unfold(70);
+        let arrangements = Arrangements::<BigUint>::new(&record);
+        assert!(arrangements.count() > BigUint::from(u128::MAX));
+
+        let last = arrangements
+            .nth(arrangements.count() - BigUint::from(1u32))
+            .unwrap();
+        assert!(record.matches(&last));
+        // the dots come first in the last arrangement
+        assert_eq!(".".repeat(70) + &"#.".repeat(69) + "#", last);
+        assert_eq!(None, arrangements.nth(arrangements.count()));
+
+        let mut rng = SplitMix64::new(2023);
+        let sample = arrangements.random(&mut rng).unwrap();
+        assert!(record.matches(&sample));
+    }
+
+    #[test]
+    fn it_samples_uniformly() {
+        let record = Record::parse(".??..??...?##. 1,1,3").unwrap();
+        let arrangements = Arrangements::<u128>::new(&record);
+        let mut rng = SplitMix64::new(2023);
+        let mut seen = [0; 4];
+        for _ in 0..4000 {
+            let arrangement = arrangements.random(&mut rng).unwrap();
+            let idx = arrangements.iter().position(|a| a == arrangement).unwrap();
+            seen[idx] += 1;
+        }
+        assert!(seen.iter().all(|&count| (900..1100).contains(&count)));
+
+        let impossible = Record::parse("#.# 3").unwrap();
+        assert_eq!(
+            None,
+            Arrangements::<u128>::new(&impossible).random(&mut rng)
+        );
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(5)]
+    #[case(20)]
+    fn it_counts_in_any_integer_type(#[case] factor: usize) {
+        let record = Record::parse(".??..??...?##. 1,1,3").unwrap();
+        let expected = Arrangements::<u128>::new(&record.unfold(factor)).count();
+        assert_eq!(expected, count_arrangements::<u128>(&record, factor));
+        assert_eq!(
+            BigUint::from(expected),
+            count_arrangements::<BigUint>(&record, factor)
+        );
+    }
+
+    #[test]
+    fn it_counts_beyond_u128() {
+        // 70 single damaged springs among 209 unknown ones: binomial(140, 70) ways
+        let record = Record::parse("?? 1").unwrap();
+        let expected = (1..=70u32).fold(BigUint::from(1u32), |acc, k| acc * (70 + k) / k);
+        assert!(expected > BigUint::from(u128::MAX));
+        assert_eq!(expected, count_arrangements::<BigUint>(&record, 70));
+    }
+
+    #[test]
+    fn it_rejects_invalid_records() {
+        assert!(Record::parse("???").is_err());
+        assert!(Record::parse("?x? 1").is_err());
+        assert!(Record::parse("??? 1,a").is_err());
+        assert!(Record::parse("??? 1,0").is_err());
+    }
+}
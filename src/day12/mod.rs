@@ -1,2 +1,4 @@
+pub mod arrangements;
+pub mod nonogram;
 pub mod part1;
 pub mod part2;
@@ -0,0 +1,309 @@
+use std::collections::VecDeque;
+use std::ops::{Add, Mul};
+
+use anyhow::{bail, Result};
+use num::{One, Zero};
+
+use super::arrangements::{damaged_runs, group_end, suffix_counts};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solutions {
+    None,
+    Unique(Vec<String>),
+    // two of the solutions, when there are more than one
+    Many(Vec<String>, Vec<String>),
+}
+
+// A 2D puzzle where every row and column is a spring record: its clue lists
+// the runs of filled (`#`) cells from left to right or from top to bottom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nonogram {
+    rows: Vec<Vec<usize>>,
+    cols: Vec<Vec<usize>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Line {
+    Row(usize),
+    Col(usize),
+}
+
+type Grid = Vec<Vec<char>>;
+
+impl Nonogram {
+    pub fn new(rows: Vec<Vec<usize>>, cols: Vec<Vec<usize>>) -> Result<Self> {
+        if rows.is_empty() || cols.is_empty() {
+            bail!("A nonogram needs at least one row and one column");
+        }
+
+        Ok(Self { rows, cols })
+    }
+
+    // the clues of a picture made of `#` and `.`
+    pub fn from_picture(picture: &[&str]) -> Result<Self> {
+        let grid = picture
+            .iter()
+            .map(|row| row.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        if grid.iter().any(|row| row.len() != grid[0].len()) {
+            bail!("The rows of the picture have different lengths");
+        }
+
+        let rows = grid.iter().map(|row| runs(row)).collect();
+        let cols = (0..grid.first().map_or(0, |row| row.len()))
+            .map(|j| runs(&grid.iter().map(|row| row[j]).collect::<Vec<_>>()))
+            .collect();
+
+        Self::new(rows, cols)
+    }
+
+    pub fn solve(&self) -> Solutions {
+        let mut grid = vec![vec!['?'; self.cols.len()]; self.rows.len()];
+        let mut found = Vec::new();
+        if self.propagate(&mut grid) {
+            self.search(grid, &mut found);
+        }
+
+        let mut found = found.into_iter().map(|grid| {
+            grid.into_iter()
+                .map(|row| row.into_iter().collect::<String>())
+                .collect::<Vec<_>>()
+        });
+        match (found.next(), found.next()) {
+            (None, _) => Solutions::None,
+            (Some(solution), None) => Solutions::Unique(solution),
+            (Some(first), Some(second)) => Solutions::Many(first, second),
+        }
+    }
+
+    // backtracks on the first unknown cell, until two solutions are found
+    fn search(&self, grid: Grid, found: &mut Vec<Grid>) {
+        let unknown = grid
+            .iter()
+            .enumerate()
+            .find_map(|(i, row)| row.iter().position(|&c| c == '?').map(|j| (i, j)));
+        let Some((i, j)) = unknown else {
+            found.push(grid);
+            return;
+        };
+
+        for guess in ['#', '.'] {
+            if found.len() >= 2 {
+                return;
+            }
+            let mut attempt = grid.clone();
+            attempt[i][j] = guess;
+            if self.propagate(&mut attempt) {
+                self.search(attempt, found);
+            }
+        }
+    }
+
+    // Line solving to a fixpoint: a cell is settled once every arrangement of
+    // one of its lines agrees on it. Returns false when a line has no arrangement.
+    fn propagate(&self, grid: &mut Grid) -> bool {
+        let mut queue = (0..self.rows.len())
+            .map(Line::Row)
+            .chain((0..self.cols.len()).map(Line::Col))
+            .collect::<VecDeque<_>>();
+        let mut queued = vec![true; self.rows.len() + self.cols.len()];
+
+        while let Some(line) = queue.pop_front() {
+            let key = match line {
+                Line::Row(i) => i,
+                Line::Col(j) => self.rows.len() + j,
+            };
+            queued[key] = false;
+
+            let (cells, clue) = match line {
+                Line::Row(i) => (grid[i].clone(), &self.rows[i]),
+                Line::Col(j) => (grid.iter().map(|row| row[j]).collect(), &self.cols[j]),
+            };
+            let Some(settled) = solve_line(cells, clue) else {
+                return false;
+            };
+
+            for (idx, c) in settled {
+                let (i, j, crossing) = match line {
+                    Line::Row(i) => (i, idx, Line::Col(idx)),
+                    Line::Col(j) => (idx, j, Line::Row(idx)),
+                };
+                grid[i][j] = c;
+                let crossing_key = match crossing {
+                    Line::Row(i) => i,
+                    Line::Col(j) => self.rows.len() + j,
+                };
+                if !queued[crossing_key] {
+                    queued[crossing_key] = true;
+                    queue.push_back(crossing);
+                }
+            }
+        }
+
+        true
+    }
+}
+
+// The unknown cells of a line which every arrangement agrees on, from one pass of
+// the spring record DP over the line and one over the line reversed.
+fn solve_line(cells: Vec<char>, clue: &[usize]) -> Option<Vec<(usize, char)>> {
+    let (n, m) = (cells.len(), clue.len());
+
+    // `suffix[g][i]`: `cells[i..]` can hold `clue[g..]`
+    let mut suffix = vec![Vec::new(); m + 1];
+    suffix_counts(&cells, clue, |g, ways: &[Possible]| {
+        suffix[g] = ways.to_vec()
+    });
+    if !suffix[0][0].0 {
+        return None;
+    }
+
+    // `prefix[g][i]`: `cells[..i]` can hold `clue[..g]`
+    let reversed = cells.iter().rev().copied().collect::<Vec<_>>();
+    let reversed_clue = clue.iter().rev().copied().collect::<Vec<_>>();
+    let mut prefix = vec![Vec::new(); m + 1];
+    suffix_counts(&reversed, &reversed_clue, |g, ways: &[Possible]| {
+        prefix[m - g] = ways.iter().rev().copied().collect();
+    });
+
+    // the cells covered by some placement of a group which the rest of the line allows
+    let run = damaged_runs(&cells);
+    let mut covered = vec![0isize; n + 1];
+    for (g, &len) in clue.iter().enumerate() {
+        for start in 0..n {
+            let before = match start {
+                0 => g == 0,
+                _ => cells[start - 1] != '#' && prefix[g][start - 1].0,
+            };
+            let after = group_end(&cells, &run, start, len).is_some_and(|end| suffix[g + 1][end].0);
+            if before && after {
+                covered[start] += 1;
+                covered[start + len] -= 1;
+            }
+        }
+    }
+
+    let mut settled = Vec::new();
+    let mut filled = 0;
+    for idx in 0..n {
+        filled += covered[idx];
+        if cells[idx] != '?' {
+            continue;
+        }
+
+        let empty = (0..=m).any(|g| prefix[g][idx].0 && suffix[g][idx + 1].0);
+        match (filled > 0, empty) {
+            (false, _) => settled.push((idx, '.')),
+            (_, false) => settled.push((idx, '#')),
+            _ => (),
+        }
+    }
+
+    Some(settled)
+}
+
+// Whether a line has any arrangement at all, counted by the DP without overflowing:
+// adding is `or` and multiplying is `and`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Possible(bool);
+
+impl Add for Possible {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 || other.0)
+    }
+}
+
+impl Mul for Possible {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self(self.0 && other.0)
+    }
+}
+
+impl Zero for Possible {
+    fn zero() -> Self {
+        Self(false)
+    }
+
+    fn is_zero(&self) -> bool {
+        !self.0
+    }
+}
+
+impl One for Possible {
+    fn one() -> Self {
+        Self(true)
+    }
+}
+
+fn runs(line: &[char]) -> Vec<usize> {
+    line.split(|&c| c != '#')
+        .filter(|run| !run.is_empty())
+        .map(|run| run.len())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_solves_by_line_solving() {
+        let picture = [".###.", "#...#", "#####", "#...#", "#...#"];
+        let nonogram = Nonogram::from_picture(&picture).unwrap();
+        assert_eq!(
+            Solutions::Unique(picture.iter().map(|row| row.to_string()).collect()),
+            nonogram.solve()
+        );
+    }
+
+    #[test]
+    fn it_backtracks_when_stuck() {
+        // line solving alone settles nothing: any permutation matrix fits
+        let nonogram = Nonogram::new(
+            vec![vec![1], vec![1], vec![1]],
+            vec![vec![1], vec![1], vec![1]],
+        )
+        .unwrap();
+        match nonogram.solve() {
+            Solutions::Many(first, second) => {
+                assert_ne!(first, second);
+                for solution in [first, second] {
+                    let rows = solution.iter().map(String::as_str).collect::<Vec<_>>();
+                    assert_eq!(nonogram, Nonogram::from_picture(&rows).unwrap());
+                }
+            }
+            solutions => panic!("expected many solutions, got {:?}", solutions),
+        }
+    }
+
+    #[test]
+    fn it_settles_the_cells_every_arrangement_agrees_on() {
+        let line = |cells: &str| cells.chars().collect::<Vec<_>>();
+        assert_eq!(
+            Some((2..8).map(|idx| (idx, '#')).collect()),
+            solve_line(line("??????????"), &[8])
+        );
+        assert_eq!(
+            Some(vec![(0, '.'), (2, '.'), (3, '.')]),
+            solve_line(line("?#??"), &[1])
+        );
+        assert_eq!(
+            Some(vec![(0, '#'), (1, '.'), (3, '#')]),
+            solve_line(line("??#?"), &[1, 2])
+        );
+        assert_eq!(Some(Vec::new()), solve_line(line("???"), &[1]));
+        assert_eq!(None, solve_line(line("#.#"), &[3]));
+    }
+
+    #[test]
+    fn it_reports_unsolvable_clues() {
+        let nonogram = Nonogram::new(vec![vec![2], vec![]], vec![vec![1], vec![]]).unwrap();
+        assert_eq!(Solutions::None, nonogram.solve());
+        assert!(Nonogram::new(Vec::new(), vec![vec![1]]).is_err());
+        assert!(Nonogram::from_picture(&["#.", "#"]).is_err());
+    }
+}
@@ -1,51 +1,11 @@
-use crate::utils::transpose;
+use anyhow::Result;
 
-pub fn process_data(input: &str) -> u32 {
-    let patterns = parse_patterns(input);
-    patterns.iter().fold(0, |acc, pattern| {
-        let mut num = 0;
+use super::pattern::summarize_all;
 
-        num += get_mirror_count(pattern) * 100;
-
-        num += get_mirror_count(transpose(pattern.to_vec()).as_slice());
-
-        acc + num as u32
-    })
+pub fn process_data(input: &str) -> Result<u32> {
+    Ok(summarize_all(input, 0)? as u32)
 }
 
-fn parse_patterns(input: &str) -> Vec<Vec<Vec<char>>> {
-    input
-        .split("\n\n")
-        .map(|s| s.lines().map(|s| s.chars().collect()).collect())
-        .collect()
-}
-
-fn get_mirror_count(pattern: &[Vec<char>]) -> usize {
-    for i in 1..pattern.len() {
-        let mut above = pattern[..i].to_vec();
-        above.reverse();
-        let below = &pattern[i..];
-
-        let len = above.len().min(below.len());
-        if above[..len] == below[..len] {
-            return i;
-        }
-    }
-    0
-}
-
-// 123456789
-//     ><
-// #.##..##.
-// ..#.##.#.
-// ##......#
-// ##......#
-// ..#.##.#.
-// ..##..##.
-// #.#.##.#.
-//     ><
-// 123456789
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,6 +28,6 @@ mod tests {
 ..##..###
 #....#..#";
 
-        assert_eq!(405, process_data(input));
+        assert_eq!(405, process_data(input).unwrap());
     }
 }
@@ -0,0 +1,421 @@
+use std::fmt::Display;
+
+use anyhow::{bail, Result};
+
+// (row, column)
+pub type Cell = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Axis {
+    // a line between two rows
+    Horizontal,
+    // a line between two columns
+    Vertical,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reflection {
+    pub axis: Axis,
+    // the number of rows above, or columns left of, the line
+    pub position: usize,
+    // the pairs of mirrored cells which differ: flipping either cell of every pair
+    // makes the reflection perfect
+    pub mismatches: Vec<(Cell, Cell)>,
+}
+
+impl Reflection {
+    pub fn smudges(&self) -> usize {
+        self.mismatches.len()
+    }
+
+    pub fn score(&self) -> usize {
+        match self.axis {
+            Axis::Horizontal => self.position * 100,
+            Axis::Vertical => self.position,
+        }
+    }
+
+    // one cell of every mismatched pair, the one above or left of the line
+    pub fn cells_to_flip(&self) -> Vec<Cell> {
+        self.mismatches.iter().map(|&(cell, _)| cell).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    rows: Lines,
+    cols: Lines,
+}
+
+impl Pattern {
+    pub fn parse(input: &str) -> Result<Self> {
+        // `true` for rocks (`#`)
+        let cells = input
+            .trim()
+            .lines()
+            .map(|line| {
+                line.trim()
+                    .chars()
+                    .map(|c| match c {
+                        '#' => Ok(true),
+                        '.' => Ok(false),
+                        _ => bail!("Invalid cell {} in pattern", c),
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if cells.is_empty() || cells[0].is_empty() {
+            bail!("Empty pattern");
+        }
+        if cells.iter().any(|row| row.len() != cells[0].len()) {
+            bail!("The rows of the pattern have different lengths");
+        }
+
+        let cols = (0..cells[0].len())
+            .map(|j| cells.iter().map(|row| row[j]).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        Ok(Self {
+            rows: Lines::pack(&cells),
+            cols: Lines::pack(&cols),
+        })
+    }
+
+    pub fn parse_all(input: &str) -> Result<Vec<Self>> {
+        input
+            .split("\n\n")
+            .filter(|block| !block.trim().is_empty())
+            .map(Self::parse)
+            .collect()
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols.len()
+    }
+
+    // every line between two rows or columns, with the cells it fails to mirror
+    pub fn reflections(&self) -> Vec<Reflection> {
+        self.lines()
+            .map(|(axis, position)| self.reflection(axis, position))
+            .collect()
+    }
+
+    // the reflections which become perfect after fixing exactly `smudges` cells
+    pub fn reflections_with_smudges(&self, smudges: usize) -> Vec<Reflection> {
+        self.lines_with_smudges(smudges)
+            .map(|(axis, position)| self.reflection(axis, position))
+            .collect()
+    }
+
+    // the score of the first reflection with exactly `smudges` smudges, horizontal first
+    pub fn summarize(&self, smudges: usize) -> Result<usize> {
+        match self.lines_with_smudges(smudges).next() {
+            Some((axis, position)) => Ok(self.reflection(axis, position).score()),
+            None => bail!("No reflection with {} smudge(s) in\n{}", smudges, self),
+        }
+    }
+
+    fn lines(&self) -> impl Iterator<Item = (Axis, usize)> {
+        let horizontal = (1..self.rows()).map(|position| (Axis::Horizontal, position));
+        let vertical = (1..self.cols()).map(|position| (Axis::Vertical, position));
+
+        horizontal.chain(vertical)
+    }
+
+    // only counts the differing bits, and gives up on a line as soon as it has too many
+    fn lines_with_smudges(&self, smudges: usize) -> impl Iterator<Item = (Axis, usize)> + '_ {
+        self.lines().filter(move |&(axis, position)| {
+            let lines = match axis {
+                Axis::Horizontal => &self.rows,
+                Axis::Vertical => &self.cols,
+            };
+            lines.smudges(position, smudges) == Some(smudges)
+        })
+    }
+
+    fn reflection(&self, axis: Axis, position: usize) -> Reflection {
+        let (lines, along) = match axis {
+            Axis::Horizontal => (&self.rows, self.cols()),
+            Axis::Vertical => (&self.cols, self.rows()),
+        };
+        let reach = position.min(lines.len() - position);
+
+        let mut mismatches = Vec::new();
+        for d in 0..reach {
+            let (a, b) = (position - 1 - d, position + d);
+            for k in 0..along {
+                if lines.get(a, k) != lines.get(b, k) {
+                    mismatches.push(match axis {
+                        Axis::Horizontal => ((a, k), (b, k)),
+                        Axis::Vertical => ((k, a), (k, b)),
+                    });
+                }
+            }
+        }
+
+        Reflection {
+            axis,
+            position,
+            mismatches,
+        }
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for i in 0..self.rows() {
+            let line = (0..self.cols())
+                .map(|j| if self.rows.get(i, j) { '#' } else { '.' })
+                .collect::<String>();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+// The rows or the columns of a pattern, each one packed into the
+// narrowest integer which holds all of its cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Lines {
+    Narrow(Vec<u64>),
+    Wide(Vec<u128>),
+    // lines of more than 128 cells
+    Chunked(Vec<Vec<u64>>),
+}
+
+impl Lines {
+    fn pack(lines: &[Vec<bool>]) -> Self {
+        match lines.first().map_or(0, |line| line.len()) {
+            0..=64 => Self::Narrow(lines.iter().map(|line| Bits::pack(line)).collect()),
+            65..=128 => Self::Wide(lines.iter().map(|line| Bits::pack(line)).collect()),
+            _ => Self::Chunked(lines.iter().map(|line| Bits::pack(line)).collect()),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Narrow(lines) => lines.len(),
+            Self::Wide(lines) => lines.len(),
+            Self::Chunked(lines) => lines.len(),
+        }
+    }
+
+    fn get(&self, line: usize, k: usize) -> bool {
+        match self {
+            Self::Narrow(lines) => lines[line].get(k),
+            Self::Wide(lines) => lines[line].get(k),
+            Self::Chunked(lines) => lines[line].get(k),
+        }
+    }
+
+    // the cells mirrored differently by the line after `position` lines,
+    // or `None` once there are more than `limit`
+    fn smudges(&self, position: usize, limit: usize) -> Option<usize> {
+        match self {
+            Self::Narrow(lines) => smudges(lines, position, limit),
+            Self::Wide(lines) => smudges(lines, position, limit),
+            Self::Chunked(lines) => smudges(lines, position, limit),
+        }
+    }
+}
+
+fn smudges<T: Bits>(lines: &[T], position: usize, limit: usize) -> Option<usize> {
+    let mut total = 0;
+    for (a, b) in lines[..position].iter().rev().zip(&lines[position..]) {
+        total += a.distance(b);
+        if total > limit {
+            return None;
+        }
+    }
+
+    Some(total)
+}
+
+// A line of cells, bit `k` holding cell `k`.
+trait Bits {
+    fn pack(cells: &[bool]) -> Self;
+
+    fn get(&self, k: usize) -> bool;
+
+    // the number of cells which differ between two lines
+    fn distance(&self, other: &Self) -> usize;
+}
+
+impl Bits for u64 {
+    fn pack(cells: &[bool]) -> Self {
+        cells
+            .iter()
+            .rev()
+            .fold(0, |bits, &rock| bits << 1 | rock as u64)
+    }
+
+    fn get(&self, k: usize) -> bool {
+        self >> k & 1 == 1
+    }
+
+    fn distance(&self, other: &Self) -> usize {
+        (self ^ other).count_ones() as usize
+    }
+}
+
+impl Bits for u128 {
+    fn pack(cells: &[bool]) -> Self {
+        cells
+            .iter()
+            .rev()
+            .fold(0, |bits, &rock| bits << 1 | rock as u128)
+    }
+
+    fn get(&self, k: usize) -> bool {
+        self >> k & 1 == 1
+    }
+
+    fn distance(&self, other: &Self) -> usize {
+        (self ^ other).count_ones() as usize
+    }
+}
+
+impl Bits for Vec<u64> {
+    fn pack(cells: &[bool]) -> Self {
+        cells.chunks(64).map(u64::pack).collect()
+    }
+
+    fn get(&self, k: usize) -> bool {
+        self[k / 64].get(k % 64)
+    }
+
+    fn distance(&self, other: &Self) -> usize {
+        self.iter().zip(other).map(|(a, b)| a.distance(b)).sum()
+    }
+}
+
+pub fn summarize_all(input: &str, smudges: usize) -> Result<usize> {
+    Pattern::parse_all(input)?
+        .iter()
+        .map(|pattern| pattern.summarize(smudges))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+    #[rstest]
+    #[case(0, 405)]
+    #[case(1, 400)]
+    fn it_summarizes(#[case] smudges: usize, #[case] expected: usize) {
+        assert_eq!(expected, summarize_all(INPUT, smudges).unwrap());
+    }
+
+    #[test]
+    fn it_lists_every_reflection_line() {
+        let patterns = Pattern::parse_all(INPUT).unwrap();
+        let reflections = patterns[0].reflections();
+        assert_eq!(6 + 8, reflections.len());
+
+        let perfect = patterns[0].reflections_with_smudges(0);
+        assert_eq!(1, perfect.len());
+        assert_eq!((Axis::Vertical, 5), (perfect[0].axis, perfect[0].position));
+
+        let smudged = patterns[0].reflections_with_smudges(1);
+        assert_eq!(1, smudged.len());
+        assert_eq!(
+            (Axis::Horizontal, 3),
+            (smudged[0].axis, smudged[0].position)
+        );
+        assert_eq!(vec![((0, 0), (5, 0))], smudged[0].mismatches);
+        assert_eq!(vec![(0, 0)], smudged[0].cells_to_flip());
+    }
+
+    #[test]
+    fn it_handles_larger_smudge_budgets() {
+        let pattern = Pattern::parse("#..\n..#").unwrap();
+        let reflections = pattern.reflections_with_smudges(2);
+        assert_eq!(1, reflections.len());
+        assert_eq!(Axis::Horizontal, reflections[0].axis);
+        assert_eq!(vec![(0, 0), (0, 2)], reflections[0].cells_to_flip());
+        assert_eq!(100, pattern.summarize(2).unwrap());
+    }
+
+    #[rstest]
+    #[case(10)]
+    #[case(100)]
+    #[case(200)]
+    fn it_packs_patterns_of_any_width(#[case] width: usize) {
+        // mirrored around the middle column, with one smudge on the first row
+        let half = width / 2;
+        let input = (0..3)
+            .map(|i| {
+                let left = (0..half)
+                    .map(|j| if (i * 3 + j * 7) % 5 < 2 { '#' } else { '.' })
+                    .collect::<String>();
+                let right = left.chars().rev().collect::<String>();
+                let mut row = left + &right;
+                if i == 0 {
+                    row.replace_range(0..1, if row.starts_with('#') { "." } else { "#" });
+                }
+                row
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let pattern = Pattern::parse(&input).unwrap();
+        assert_eq!((3, width), (pattern.rows(), pattern.cols()));
+        assert_eq!(input + "\n", pattern.to_string());
+        match width {
+            0..=64 => assert!(matches!(pattern.rows, Lines::Narrow(_))),
+            65..=128 => assert!(matches!(pattern.rows, Lines::Wide(_))),
+            _ => assert!(matches!(pattern.rows, Lines::Chunked(_))),
+        }
+
+        let smudged = pattern.reflections_with_smudges(1);
+        let reflection = smudged
+            .iter()
+            .find(|reflection| (reflection.axis, reflection.position) == (Axis::Vertical, half))
+            .unwrap();
+        assert_eq!(vec![((0, 0), (0, width - 1))], reflection.mismatches);
+        assert!(pattern
+            .reflections_with_smudges(0)
+            .iter()
+            .all(|reflection| reflection.position != half || reflection.axis != Axis::Vertical));
+    }
+
+    #[test]
+    fn it_reports_patterns_without_reflection() {
+        let pattern = Pattern::parse("#.\n..").unwrap();
+        assert!(pattern.reflections_with_smudges(0).is_empty());
+        let error = pattern.summarize(0).unwrap_err();
+        assert_eq!(
+            "No reflection with 0 smudge(s) in\n#.\n..\n",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_patterns() {
+        assert!(Pattern::parse("#.\n#").is_err());
+        assert!(Pattern::parse("#x").is_err());
+        assert!(Pattern::parse("").is_err());
+    }
+}
@@ -0,0 +1,339 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use super::platform::{Cycle, Direction, Platform, SPIN};
+
+const EMPTY_COLOUR: &str = "#1d3557";
+const CUBE_COLOUR: &str = "#8d99ae";
+const ROUNDED_COLOUR: &str = "#f1faee";
+// the background of the states seen again, once the cycle is detected
+const LOOPING_COLOUR: &str = "#2a9d8f";
+// the background of the cycle entry, when it comes back
+const HIGHLIGHT_COLOUR: &str = "#e63946";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    // no state seen twice yet, so nothing is known about the cycle
+    Searching,
+    // the same state as the `offset`-th one of the repeating part, 0 being the cycle entry:
+    // the cycle is detected from the first of them
+    Repeat { offset: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    // the number of whole tilt sequences applied so far
+    pub applications: usize,
+    // the tilt just applied, when there is a frame per tilt
+    pub tilt: Option<Direction>,
+    pub platform: Platform,
+    pub cycle: Cycle,
+    pub phase: Phase,
+}
+
+impl Frame {
+    pub fn caption(&self) -> String {
+        let mut caption = format!("sequence {}", self.applications);
+        if let Some(tilt) = self.tilt {
+            write!(caption, ", tilt {}", tilt.to_char()).unwrap();
+        }
+        match self.phase {
+            Phase::Searching => (),
+            Phase::Repeat { offset: 0 } => write!(
+                caption,
+                " - back to the cycle entry at sequence {}, period {}",
+                self.cycle.start, self.cycle.period
+            )
+            .unwrap(),
+            Phase::Repeat { offset } => write!(
+                caption,
+                " - repeats sequence {}, period {}",
+                self.cycle.start + offset,
+                self.cycle.period
+            )
+            .unwrap(),
+        }
+
+        caption
+    }
+}
+
+// The initial platform then a frame after every tilt, or after every whole sequence,
+// until `sequence` has been applied `applications` times.
+pub fn frames(
+    platform: &Platform,
+    sequence: &[Direction],
+    applications: usize,
+    per_tilt: bool,
+) -> Vec<Frame> {
+    let cycle = platform.cycle(sequence);
+    let frame = |applications: usize, tilt: Option<Direction>, platform: &Platform| Frame {
+        applications,
+        tilt,
+        platform: platform.clone(),
+        cycle,
+        phase: phase(cycle, applications),
+    };
+
+    let mut platform = platform.clone();
+    let mut frames = vec![frame(0, None, &platform)];
+    for applied in 1..=applications {
+        for (k, &direction) in sequence.iter().enumerate() {
+            platform.tilt(direction);
+            if per_tilt {
+                // the phase only changes once the whole sequence is applied
+                let done = if k + 1 == sequence.len() {
+                    applied
+                } else {
+                    applied - 1
+                };
+                frames.push(frame(done, Some(direction), &platform));
+            }
+        }
+        if !per_tilt {
+            frames.push(frame(applied, None, &platform));
+        }
+    }
+
+    frames
+}
+
+// the cycle is only known once the first repeated state shows up
+fn phase(cycle: Cycle, applications: usize) -> Phase {
+    let Cycle { start, period } = cycle;
+    match applications {
+        n if n < start + period => Phase::Searching,
+        n => Phase::Repeat {
+            offset: (n - start) % period,
+        },
+    }
+}
+
+// The caption then the platform, with coloured rocks and a background
+// following the phase when `ansi` is set.
+pub fn to_terminal(frame: &Frame, ansi: bool) -> String {
+    let mut output = frame.caption();
+    output.push('\n');
+    let background = rgb(background(frame.phase));
+    for i in 0..frame.platform.rows() {
+        for j in 0..frame.platform.cols() {
+            let c = frame.platform.cell(i, j);
+            if ansi {
+                let (r, g, b) = rgb(colour(c).unwrap_or(EMPTY_COLOUR));
+                write!(
+                    output,
+                    "\x1b[48;2;{};{};{}m\x1b[38;2;{};{};{}m{}",
+                    background.0, background.1, background.2, r, g, b, c
+                )
+                .unwrap();
+            } else {
+                output.push(c);
+            }
+        }
+        if ansi {
+            output.push_str("\x1b[0m");
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+// every frame on a cleared screen, to be printed one after the other
+pub fn to_terminal_animation(frames: &[Frame]) -> Vec<String> {
+    frames
+        .iter()
+        .map(|frame| format!("\x1b[H\x1b[2J{}", to_terminal(frame, true)))
+        .collect()
+}
+
+// A binary PPM image where every cell is a `scale` x `scale` square,
+// empty cells painted in the colour of the phase.
+pub fn to_ppm(frame: &Frame, scale: usize) -> Vec<u8> {
+    let (rows, cols) = (frame.platform.rows(), frame.platform.cols());
+    let mut output = format!("P6\n{} {}\n255\n", cols * scale, rows * scale).into_bytes();
+    for i in 0..rows * scale {
+        for j in 0..cols * scale {
+            let c = frame.platform.cell(i / scale, j / scale);
+            let (r, g, b) = rgb(colour(c).unwrap_or(background(frame.phase)));
+            output.extend([r, g, b]);
+        }
+    }
+
+    output
+}
+
+// writes `frame-0000.ppm`, `frame-0001.ppm`... into `dir`, PPM being the only image format
+// supported
+pub fn write_ppm_frames(frames: &[Frame], dir: &Path, scale: usize) -> Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+    frames
+        .iter()
+        .enumerate()
+        .map(|(idx, frame)| {
+            let path = dir.join(format!("frame-{:04}.ppm", idx));
+            std::fs::write(&path, to_ppm(frame, scale))?;
+            Ok(path)
+        })
+        .collect()
+}
+
+// A frame per spin cycle, until the first repeated state, as PPM images in `dir`:
+// there is no PNG output.
+pub fn animate(input: &str, dir: &Path) -> Result<String> {
+    let platform = Platform::parse(input)?;
+    let cycle = platform.cycle(&SPIN);
+    let frames = frames(&platform, &SPIN, cycle.start + cycle.period, false);
+    let paths = write_ppm_frames(&frames, dir, 4)?;
+
+    Ok(format!(
+        "{} frames in {}, cycle entry at {}, period {}",
+        paths.len(),
+        dir.display(),
+        cycle.start,
+        cycle.period
+    ))
+}
+
+fn colour(c: char) -> Option<&'static str> {
+    match c {
+        'O' => Some(ROUNDED_COLOUR),
+        '#' => Some(CUBE_COLOUR),
+        _ => None,
+    }
+}
+
+fn background(phase: Phase) -> &'static str {
+    match phase {
+        Phase::Searching => EMPTY_COLOUR,
+        Phase::Repeat { offset: 0 } => HIGHLIGHT_COLOUR,
+        Phase::Repeat { .. } => LOOPING_COLOUR,
+    }
+}
+
+fn rgb(colour: &str) -> (u8, u8, u8) {
+    let channel = |at: usize| u8::from_str_radix(&colour[at..at + 2], 16).unwrap_or(0);
+    (channel(1), channel(3), channel(5))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    #[test]
+    fn it_marks_the_cycle_in_frames() {
+        let platform = Platform::parse(INPUT).unwrap();
+        let frames = frames(&platform, &SPIN, 11, false);
+        assert_eq!(12, frames.len());
+
+        // nothing is labelled before the entry state comes back
+        let phases = frames.iter().map(|frame| frame.phase).collect::<Vec<_>>();
+        assert!(phases[..10].iter().all(|&phase| phase == Phase::Searching));
+        assert_eq!(Phase::Repeat { offset: 0 }, phases[10]);
+        assert_eq!(Phase::Repeat { offset: 1 }, phases[11]);
+        assert_eq!(frames[3].platform, frames[10].platform);
+        assert_eq!(frames[4].platform, frames[11].platform);
+
+        assert_eq!("sequence 0", frames[0].caption());
+        assert_eq!("sequence 3", frames[3].caption());
+        assert_eq!(
+            "sequence 10 - back to the cycle entry at sequence 3, period 7",
+            frames[10].caption()
+        );
+        assert_eq!(
+            "sequence 11 - repeats sequence 4, period 7",
+            frames[11].caption()
+        );
+    }
+
+    #[test]
+    fn it_has_a_frame_per_tilt() {
+        let platform = Platform::parse(INPUT).unwrap();
+        let frames = frames(&platform, &SPIN, 2, true);
+        assert_eq!(1 + 2 * 4, frames.len());
+        assert_eq!(
+            vec![None, Some('N'), Some('W'), Some('S'), Some('E')],
+            frames[..5]
+                .iter()
+                .map(|frame| frame.tilt.map(Direction::to_char))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(vec![0, 0, 0, 0, 1, 1, 1, 1, 2], {
+            frames
+                .iter()
+                .map(|frame| frame.applications)
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(platform.spin(&SPIN, 2), frames[8].platform);
+        assert_eq!("sequence 1, tilt E", frames[4].caption());
+    }
+
+    #[test]
+    fn it_draws_terminal_frames() {
+        let platform = Platform::parse(INPUT).unwrap();
+        let frames = frames(&platform, &SPIN, 1, false);
+        assert_eq!(
+            format!("sequence 0\n{}", platform),
+            to_terminal(&frames[0], false)
+        );
+
+        let animation = to_terminal_animation(&frames);
+        assert_eq!(2, animation.len());
+        assert!(animation[1].starts_with("\x1b[H\x1b[2Jsequence 1\n"));
+        assert_eq!(11, animation[1].lines().count());
+    }
+
+    #[test]
+    fn it_writes_ppm_images() {
+        let platform = Platform::parse("O#.").unwrap();
+        let frames = frames(&platform, &[Direction::East], 1, false);
+
+        let image = to_ppm(&frames[0], 2);
+        let header = b"P6\n6 2\n255\n";
+        assert_eq!(header, &image[..header.len()]);
+        assert_eq!(header.len() + 6 * 2 * 3, image.len());
+        let pixels = &image[header.len()..];
+        assert_eq!([0xf1, 0xfa, 0xee], pixels[0..3]);
+        assert_eq!([0x8d, 0x99, 0xae], pixels[6..9]);
+        assert_eq!([0x1d, 0x35, 0x57], pixels[12..15]);
+        // the first tilt changes nothing: the cycle entry is seen again
+        let pixels = &to_ppm(&frames[1], 2)[header.len()..];
+        assert_eq!([0xe6, 0x39, 0x46], pixels[12..15]);
+
+        let dir = TempDir::new("day14-animation-test");
+        let paths = write_ppm_frames(&frames, &dir.0, 1).unwrap();
+        assert_eq!(dir.0.join("frame-0001.ppm"), paths[1]);
+        assert_eq!(to_ppm(&frames[1], 1), std::fs::read(&paths[1]).unwrap());
+    }
+
+    // a directory of its own for each test run, removed even when the test fails
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let nanos = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_nanos());
+            Self(std::env::temp_dir().join(format!("{}-{}-{}", name, std::process::id(), nanos)))
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+}
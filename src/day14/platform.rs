@@ -0,0 +1,357 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+
+use anyhow::{bail, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    West,
+    South,
+    East,
+}
+
+// one spin cycle of the parabolic reflector dish
+pub const SPIN: [Direction; 4] = [
+    Direction::North,
+    Direction::West,
+    Direction::South,
+    Direction::East,
+];
+
+impl Direction {
+    pub fn from_char(c: char) -> Result<Self> {
+        match c {
+            'N' => Ok(Self::North),
+            'W' => Ok(Self::West),
+            'S' => Ok(Self::South),
+            'E' => Ok(Self::East),
+            _ => bail!("Invalid direction {}", c),
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Self::North => 'N',
+            Self::West => 'W',
+            Self::South => 'S',
+            Self::East => 'E',
+        }
+    }
+}
+
+// a tilt sequence such as `NWSE`
+pub fn parse_sequence(input: &str) -> Result<Vec<Direction>> {
+    input.trim().chars().map(Direction::from_char).collect()
+}
+
+// The states reached by tilting a platform with the same sequence over and over
+// repeat from `start` applications of the sequence, every `period` applications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+// `O` rounded rocks, `#` cube-shaped rocks and `.` empty spaces, row after row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Platform {
+    rows: usize,
+    cols: usize,
+    cells: Vec<u8>,
+}
+
+impl Platform {
+    pub fn parse(input: &str) -> Result<Self> {
+        let lines = input.trim().lines().map(str::trim).collect::<Vec<_>>();
+        let cols = lines.first().map_or(0, |line| line.len());
+        if cols == 0 {
+            bail!("Empty platform");
+        }
+
+        let mut cells = Vec::with_capacity(lines.len() * cols);
+        for (i, line) in lines.iter().enumerate() {
+            if line.len() != cols {
+                bail!("Row {} has {} columns instead of {}", i, line.len(), cols);
+            }
+            if let Some(c) = line.chars().find(|c| !"O#.".contains(*c)) {
+                bail!("Invalid cell {} in row {}", c, i);
+            }
+            cells.extend(line.bytes());
+        }
+
+        Ok(Self {
+            rows: lines.len(),
+            cols,
+            cells,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn cell(&self, i: usize, j: usize) -> char {
+        self.cells[i * self.cols + j] as char
+    }
+
+    // rolls every rounded rock as far as it goes towards `direction`
+    pub fn tilt(&mut self, direction: Direction) {
+        let (rows, cols) = (self.rows, self.cols);
+        match direction {
+            Direction::North => (0..cols).for_each(|j| self.tilt_lane(j, cols as isize, rows)),
+            Direction::South => (0..cols)
+                .for_each(|j| self.tilt_lane((rows - 1) * cols + j, -(cols as isize), rows)),
+            Direction::West => (0..rows).for_each(|i| self.tilt_lane(i * cols, 1, cols)),
+            Direction::East => {
+                (0..rows).for_each(|i| self.tilt_lane(i * cols + cols - 1, -1, cols))
+            }
+        }
+    }
+
+    pub fn tilt_all(&mut self, sequence: &[Direction]) {
+        sequence.iter().for_each(|&direction| self.tilt(direction));
+    }
+
+    // each rounded rock weighs the number of rows or columns from it to the opposite wall
+    pub fn load(&self, wall: Direction) -> usize {
+        self.rounded_rocks()
+            .map(|(i, j)| match wall {
+                Direction::North => self.rows - i,
+                Direction::South => i + 1,
+                Direction::West => self.cols - j,
+                Direction::East => j + 1,
+            })
+            .sum()
+    }
+
+    pub fn rounded_rocks(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, &c)| c == b'O')
+            .map(|(idx, _)| (idx / self.cols, idx % self.cols))
+    }
+
+    // the platform after applying `sequence` `times` times, skipping whole cycles
+    pub fn spin(&self, sequence: &[Direction], times: usize) -> Self {
+        let mut platform = self.clone();
+        let mut seen = HashMap::from([(platform.state_hash(), 0)]);
+        for step in 1..=times {
+            platform.tilt_all(sequence);
+            if let Some(Cycle { start, period }) = self.repeat(sequence, &platform, step, &mut seen)
+            {
+                // back to the state after `start` applications
+                for _ in 0..(times - start) % period {
+                    platform.tilt_all(sequence);
+                }
+                return platform;
+            }
+        }
+
+        platform
+    }
+
+    pub fn cycle(&self, sequence: &[Direction]) -> Cycle {
+        let mut platform = self.clone();
+        let mut seen = HashMap::from([(platform.state_hash(), 0)]);
+        // a platform only has finitely many states, so one of them comes back
+        let mut step = 0;
+        loop {
+            step += 1;
+            platform.tilt_all(sequence);
+            if let Some(cycle) = self.repeat(sequence, &platform, step, &mut seen) {
+                return cycle;
+            }
+        }
+    }
+
+    // The cycle closed by `platform`, the state after `step` applications, if it was seen
+    // before. Only the hashes of the states are kept, so a match is checked against the
+    // state replayed from `self`.
+    fn repeat(
+        &self,
+        sequence: &[Direction],
+        platform: &Self,
+        step: usize,
+        seen: &mut HashMap<u64, usize>,
+    ) -> Option<Cycle> {
+        let hash = platform.state_hash();
+        if let Some(&start) = seen.get(&hash) {
+            let mut replayed = self.clone();
+            (0..start).for_each(|_| replayed.tilt_all(sequence));
+            if replayed.cells == platform.cells {
+                let period = step - start;
+                return Some(Cycle { start, period });
+            }
+        }
+        seen.insert(hash, step);
+
+        None
+    }
+
+    fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.cells.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // The `len` cells from `start`, `step` apart, starting from the wall the rocks roll to.
+    // `free` is the first cell a rock can stop in.
+    fn tilt_lane(&mut self, start: usize, step: isize, len: usize) {
+        let at = |k: usize| (start as isize + k as isize * step) as usize;
+        let mut free = 0;
+        for k in 0..len {
+            match self.cells[at(k)] {
+                b'#' => free = k + 1,
+                b'O' => {
+                    self.cells[at(k)] = b'.';
+                    self.cells[at(free)] = b'O';
+                    free += 1;
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.cells.chunks(self.cols) {
+            writeln!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    #[test]
+    fn it_tilts_north() {
+        let mut platform = Platform::parse(INPUT).unwrap();
+        platform.tilt(Direction::North);
+        assert_eq!(
+            "OOOO.#.O..
+OO..#....#
+OO..O##..O
+O..#.OO...
+........#.
+..#....#.#
+..O..#.O.O
+..O.......
+#....###..
+#....#....
+",
+            platform.to_string()
+        );
+        assert_eq!(136, platform.load(Direction::North));
+    }
+
+    #[rstest]
+    #[case(1, ".....#....\n....#...O#\n...OO##...\n.OO#......\n.....OOO#.\n.O#...O#.#\n....O#....\n......OOOO\n#...O###..\n#..OO#....\n")]
+    #[case(2, ".....#....\n....#...O#\n.....##...\n..O#......\n.....OOO#.\n.O#...O#.#\n....O#...O\n.......OOO\n#..OO###..\n#.OOO#...O\n")]
+    #[case(3, ".....#....\n....#...O#\n.....##...\n..O#......\n.....OOO#.\n.O#...O#.#\n....O#...O\n.......OOO\n#...O###.O\n#.OOO#...O\n")]
+    fn it_spins(#[case] times: usize, #[case] expected: &str) {
+        let platform = Platform::parse(INPUT).unwrap();
+        assert_eq!(expected, platform.spin(&SPIN, times).to_string());
+    }
+
+    #[test]
+    fn it_skips_repeated_cycles() {
+        let platform = Platform::parse(INPUT).unwrap();
+        let cycle = platform.cycle(&SPIN);
+        assert_eq!(
+            Cycle {
+                start: 3,
+                period: 7
+            },
+            cycle
+        );
+
+        let mut expected = platform.clone();
+        for times in 0..30 {
+            assert_eq!(expected, platform.spin(&SPIN, times));
+            expected.tilt_all(&SPIN);
+        }
+        assert_eq!(
+            64,
+            platform.spin(&SPIN, 1_000_000_000).load(Direction::North)
+        );
+    }
+
+    #[rstest]
+    #[case(Direction::North, Direction::South)]
+    #[case(Direction::West, Direction::East)]
+    fn it_measures_the_load_on_any_wall(#[case] wall: Direction, #[case] opposite: Direction) {
+        // the same rocks seen in a mirror, the wall swapping with the opposite one
+        let mirrored = match wall {
+            Direction::North | Direction::South => {
+                INPUT.lines().rev().collect::<Vec<_>>().join("\n")
+            }
+            Direction::West | Direction::East => INPUT
+                .lines()
+                .map(|line| line.chars().rev().collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+
+        let mut platform = Platform::parse(INPUT).unwrap();
+        let mut mirrored = Platform::parse(&mirrored).unwrap();
+        platform.tilt(opposite);
+        mirrored.tilt(wall);
+        assert_eq!(platform.load(opposite), mirrored.load(wall));
+        assert_eq!(platform.load(wall), mirrored.load(opposite));
+    }
+
+    #[test]
+    fn it_follows_arbitrary_sequences() {
+        let sequence = parse_sequence("ES").unwrap();
+        assert_eq!(
+            "ES",
+            sequence.iter().map(|d| d.to_char()).collect::<String>()
+        );
+
+        let mut platform = Platform::parse("O.#\n.O.\n..O").unwrap();
+        platform.tilt_all(&sequence);
+        assert_eq!("..#\n..O\n.OO\n", platform.to_string());
+        assert_eq!(4, platform.load(Direction::North));
+        assert_eq!(8, platform.load(Direction::East));
+
+        assert_eq!(
+            Cycle {
+                start: 0,
+                period: 1
+            },
+            platform.cycle(&sequence)
+        );
+        assert!(parse_sequence("NX").is_err());
+    }
+
+    #[test]
+    fn it_rejects_invalid_platforms() {
+        assert!(Platform::parse("O.\n.").is_err());
+        assert!(Platform::parse("O?").is_err());
+        assert!(Platform::parse("").is_err());
+    }
+}
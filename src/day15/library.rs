@@ -0,0 +1,284 @@
+use std::fmt::{Display, Write};
+
+use anyhow::{anyhow, bail, Result};
+
+use super::part1::hash_str;
+
+const BOXES: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step<'a> {
+    // `label-`
+    Remove(&'a str),
+    // `label=focal_length`
+    Insert(&'a str, u8),
+}
+
+impl<'a> Step<'a> {
+    pub fn parse(input: &'a str) -> Result<Self> {
+        if let Some(label) = input.strip_suffix('-') {
+            return Ok(Self::Remove(label));
+        }
+
+        let (label, focal_length) = input
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Invalid step {}", input))?;
+        let focal_length = focal_length
+            .parse()
+            .map_err(|e| anyhow!("Invalid focal length in {}: {}", input, e))?;
+        Ok(Self::Insert(label, focal_length))
+    }
+
+    pub fn parse_all(input: &'a str) -> Result<Vec<Self>> {
+        input.trim_end().split(',').map(Self::parse).collect()
+    }
+
+    pub fn label(&self) -> &'a str {
+        match self {
+            Self::Remove(label) | Self::Insert(label, _) => label,
+        }
+    }
+}
+
+impl Display for Step<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Remove(label) => write!(f, "{}-", label),
+            Self::Insert(label, focal_length) => write!(f, "{}={}", label, focal_length),
+        }
+    }
+}
+
+// The 256 boxes of the HASHMAP procedure, each one holding labelled lenses
+// in the order they were inserted. A label goes in the box its HASH points to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LensLibrary {
+    boxes: Vec<Vec<(String, u8)>>,
+}
+
+impl Default for LensLibrary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LensLibrary {
+    pub fn new() -> Self {
+        Self {
+            boxes: vec![Vec::new(); BOXES],
+        }
+    }
+
+    // replaces the lens with the same label in place, or adds one at the back of its box,
+    // returning the focal length it replaced
+    pub fn insert(&mut self, label: &str, focal_length: u8) -> Option<u8> {
+        let lenses = &mut self.boxes[hash_str(label) as usize];
+        match lenses.iter_mut().find(|(l, _)| l == label) {
+            Some((_, old)) => Some(std::mem::replace(old, focal_length)),
+            None => {
+                lenses.push((label.to_string(), focal_length));
+                None
+            }
+        }
+    }
+
+    // the lenses behind the removed one move forward
+    pub fn remove(&mut self, label: &str) -> Option<u8> {
+        let lenses = &mut self.boxes[hash_str(label) as usize];
+        let idx = lenses.iter().position(|(l, _)| l == label)?;
+        Some(lenses.remove(idx).1)
+    }
+
+    pub fn get(&self, label: &str) -> Option<u8> {
+        self.boxes[hash_str(label) as usize]
+            .iter()
+            .find(|(l, _)| l == label)
+            .map(|&(_, focal_length)| focal_length)
+    }
+
+    pub fn apply(&mut self, step: &Step) {
+        match *step {
+            Step::Remove(label) => {
+                self.remove(label);
+            }
+            Step::Insert(label, focal_length) => {
+                self.insert(label, focal_length);
+            }
+        }
+    }
+
+    // (box, slot, label, focal length) of every lens, box after box, both from 0
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &str, u8)> + '_ {
+        self.boxes.iter().enumerate().flat_map(|(b, lenses)| {
+            lenses
+                .iter()
+                .enumerate()
+                .map(move |(slot, (label, focal_length))| (b, slot, label.as_str(), *focal_length))
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.boxes.iter().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boxes.iter().all(Vec::is_empty)
+    }
+
+    pub fn focusing_power(&self) -> u64 {
+        self.iter()
+            .map(|(b, slot, _, focal_length)| {
+                (b as u64 + 1) * (slot as u64 + 1) * focal_length as u64
+            })
+            .sum()
+    }
+}
+
+// the boxes holding lenses, as in `Box 0: [rn 1] [cm 2]`
+impl Display for LensLibrary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (b, lenses) in self.boxes.iter().enumerate() {
+            if lenses.is_empty() {
+                continue;
+            }
+            write!(f, "Box {}:", b)?;
+            for (label, focal_length) in lenses {
+                write!(f, " [{} {}]", label, focal_length)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn initialize(input: &str) -> Result<LensLibrary> {
+    let mut library = LensLibrary::new();
+    for step in Step::parse_all(input)? {
+        library.apply(&step);
+    }
+
+    Ok(library)
+}
+
+// the boxes after every step, as listed in the puzzle
+pub fn trace(input: &str) -> Result<String> {
+    let steps = Step::parse_all(input)?;
+    if steps.is_empty() {
+        bail!("No steps to trace");
+    }
+
+    let mut library = LensLibrary::new();
+    let mut output = String::new();
+    for (idx, step) in steps.iter().enumerate() {
+        library.apply(step);
+        if idx > 0 {
+            output.push('\n');
+        }
+        write!(output, "After \"{}\":\n{}", step, library).unwrap();
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    #[test]
+    fn it_traces_every_step() {
+        let expected = r#"After "rn=1":
+Box 0: [rn 1]
+
+After "cm-":
+Box 0: [rn 1]
+
+After "qp=3":
+Box 0: [rn 1]
+Box 1: [qp 3]
+
+After "cm=2":
+Box 0: [rn 1] [cm 2]
+Box 1: [qp 3]
+
+After "qp-":
+Box 0: [rn 1] [cm 2]
+
+After "pc=4":
+Box 0: [rn 1] [cm 2]
+Box 3: [pc 4]
+
+After "ot=9":
+Box 0: [rn 1] [cm 2]
+Box 3: [pc 4] [ot 9]
+
+After "ab=5":
+Box 0: [rn 1] [cm 2]
+Box 3: [pc 4] [ot 9] [ab 5]
+
+After "pc-":
+Box 0: [rn 1] [cm 2]
+Box 3: [ot 9] [ab 5]
+
+After "pc=6":
+Box 0: [rn 1] [cm 2]
+Box 3: [ot 9] [ab 5] [pc 6]
+
+After "ot=7":
+Box 0: [rn 1] [cm 2]
+Box 3: [ot 7] [ab 5] [pc 6]
+"#;
+        assert_eq!(expected, trace(INPUT).unwrap());
+    }
+
+    #[test]
+    fn it_inserts_gets_and_removes_lenses() {
+        let mut library = initialize(INPUT).unwrap();
+        assert_eq!(145, library.focusing_power());
+        assert_eq!(5, library.len());
+        assert_eq!(Some(7), library.get("ot"));
+        assert_eq!(None, library.get("qp"));
+
+        assert_eq!(Some(7), library.insert("ot", 1));
+        assert_eq!(None, library.insert("qp", 3));
+        assert_eq!(Some(2), library.remove("cm"));
+        assert_eq!(None, library.remove("cm"));
+        assert_eq!(
+            vec![
+                (0, 0, "rn", 1),
+                (1, 0, "qp", 3),
+                (3, 0, "ot", 1),
+                (3, 1, "ab", 5),
+                (3, 2, "pc", 6)
+            ],
+            library.iter().collect::<Vec<_>>()
+        );
+
+        ["rn", "qp", "ot", "ab", "pc"].iter().for_each(|label| {
+            library.remove(label);
+        });
+        assert!(library.is_empty());
+        assert_eq!(0, library.focusing_power());
+    }
+
+    #[test]
+    fn it_parses_steps() {
+        let steps = Step::parse_all(INPUT).unwrap();
+        assert_eq!(Step::Insert("rn", 1), steps[0]);
+        assert_eq!(Step::Remove("cm"), steps[1]);
+        assert_eq!("cm", steps[3].label());
+        assert_eq!(
+            INPUT,
+            steps
+                .iter()
+                .map(|step| step.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+
+        assert!(Step::parse("rn").is_err());
+        assert!(Step::parse("rn=x").is_err());
+        assert!(trace("").is_err());
+    }
+}
@@ -1,10 +1,33 @@
+use std::hash::Hasher;
+
 pub fn process_data(input: &str) -> u64 {
     input.trim_end().split(',').map(hash_str).sum()
 }
 
-fn hash_str(input: &str) -> u64 {
-    let v = input.chars().fold(0, calculate_current_value);
-    custom_hash(v)
+pub fn hash_str(input: &str) -> u64 {
+    let mut hasher = HolidayHasher::default();
+    hasher.write(input.as_bytes());
+    hasher.finish()
+}
+
+// The Holiday ASCII String Helper algorithm, one byte at a time.
+// Only `write` follows the algorithm: hashing a `str` through `Hash` also writes a
+// terminating byte, so labels are hashed with `hash_str`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HolidayHasher {
+    current_value: u64,
+}
+
+impl Hasher for HolidayHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.current_value = bytes.iter().fold(self.current_value, |v, &byte| {
+            calculate_current_value(v, byte as char)
+        });
+    }
+
+    fn finish(&self) -> u64 {
+        custom_hash(self.current_value)
+    }
 }
 
 fn calculate_current_value(current_value: u64, character: char) -> u64 {
@@ -37,10 +60,26 @@ mod tests {
     #[case("pc=4", 180)]
     #[case("ot=9", 9)]
     #[case("pc-", 48)]
+    #[case("rn", 0)]
+    #[case("cm", 0)]
+    #[case("qp", 1)]
+    #[case("pc", 3)]
+    #[case("ot", 3)]
+    #[case("ab", 3)]
     fn test_hash_str(#[case] input: &str, #[case] expected: u64) {
         assert_eq!(expected, hash_str(input));
     }
 
+    #[test]
+    fn test_holiday_hasher() {
+        let mut hasher = HolidayHasher::default();
+        hasher.write(b"HA");
+        hasher.write_u8(b'S');
+        hasher.write(b"H");
+        assert_eq!(52, hasher.finish());
+        assert_eq!(0, HolidayHasher::default().finish());
+    }
+
     #[test]
     fn test_calculate_current_value() {
         let mut v = calculate_current_value(0, 'H');
@@ -14,6 +14,9 @@ fn main() -> anyhow::Result<()> {
     #[cfg(feature = "day02-part2")]
     run_day02_part2()?;
 
+    #[cfg(feature = "day02-stats")]
+    run_day02_stats()?;
+
     #[cfg(feature = "day03-part1")]
     run_day03_part1()?;
 
@@ -32,6 +35,9 @@ fn main() -> anyhow::Result<()> {
     #[cfg(feature = "day05-part2")]
     run_day05_part2()?;
 
+    #[cfg(feature = "day05-validate")]
+    run_day05_validate()?;
+
     #[cfg(feature = "day06-part1")]
     run_day06_part1()?;
 
@@ -44,6 +50,9 @@ fn main() -> anyhow::Result<()> {
     #[cfg(feature = "day07-part2")]
     run_day07_part2()?;
 
+    #[cfg(feature = "day07-table")]
+    run_day07_table()?;
+
     #[cfg(feature = "day08-part1")]
     run_day08_part1()?;
 
@@ -65,11 +74,20 @@ fn main() -> anyhow::Result<()> {
     #[cfg(feature = "day10-part2")]
     run_day10_part2()?;
 
+    #[cfg(feature = "day10-part2-flood")]
+    run_day10_part2_flood()?;
+
+    #[cfg(feature = "day10-render")]
+    run_day10_render()?;
+
+    #[cfg(feature = "day10-render-svg")]
+    run_day10_render_svg()?;
+
     #[cfg(feature = "day11-part1")]
-    run_day11_part1();
+    run_day11_part1()?;
 
     #[cfg(feature = "day11-part2")]
-    run_day11_part2();
+    run_day11_part2()?;
 
     #[cfg(feature = "day12-part1")]
     run_day12_part1()?;
@@ -78,22 +96,25 @@ fn main() -> anyhow::Result<()> {
     run_day12_part2()?;
 
     #[cfg(feature = "day13-part1")]
-    run_day13_part1();
+    run_day13_part1()?;
 
     #[cfg(feature = "day13-part2")]
-    run_day13_part2();
+    run_day13_part2()?;
 
     #[cfg(feature = "day14-part1")]
-    run_day14_part1();
+    run_day14_part1()?;
 
     #[cfg(feature = "day14-part2")]
-    run_day14_part2();
+    run_day14_part2()?;
+
+    #[cfg(feature = "day14-animate")]
+    run_day14_animate()?;
 
     #[cfg(feature = "day15-part1")]
     run_day15_part1();
 
     #[cfg(feature = "day15-part2")]
-    run_day15_part2();
+    run_day15_part2()?;
 
     #[cfg(feature = "day16-part1")]
     run_day16_part1();
@@ -191,6 +212,17 @@ fn run_day02_part2() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "day02-stats")]
+fn run_day02_stats() -> anyhow::Result<()> {
+    let input_strings = include_str!("../inputs/day02-input.txt");
+    let cube_game = day02::cube_game::CubeGame::parse(input_strings)?;
+    let stats = day02::stats::Stats::new(&cube_game, 50);
+    println!("daily02-stats =\n{}", stats);
+    println!("daily02-stats-json = {}", stats.to_json());
+
+    Ok(())
+}
+
 #[cfg(feature = "day03-part1")]
 fn run_day03_part1() -> anyhow::Result<()> {
     let input_strings = include_str!("../inputs/day03-input.txt");
@@ -234,11 +266,27 @@ fn run_day05_part1() -> anyhow::Result<()> {
 #[cfg(feature = "day05-part2")]
 fn run_day05_part2() -> anyhow::Result<()> {
     let input_strings = include_str!("../inputs/day05-input.txt");
-    let result = day05::part2::process_data(input_strings)?;
+    let almanac = day05::almanac::Almanac::parse(input_strings)?;
+    let (result, warnings) = day05::part2::solve(&almanac)?;
+    for warning in warnings {
+        eprintln!("daily05-part2 warning: {}", warning);
+    }
     println!("daily05-part2 = {}", result); // 56931769
     Ok(())
 }
 
+#[cfg(feature = "day05-validate")]
+fn run_day05_validate() -> anyhow::Result<()> {
+    let input_strings = include_str!("../inputs/day05-input.txt");
+    let almanac = day05::almanac::Almanac::parse(input_strings)?;
+    let warnings = day05::validation::validate(&almanac);
+    println!("daily05-validate = {} warning(s)", warnings.len());
+    for warning in warnings {
+        println!("  {}", warning);
+    }
+    Ok(())
+}
+
 #[cfg(feature = "day06-part1")]
 fn run_day06_part1() -> anyhow::Result<()> {
     let input_strings = include_str!("../inputs/day06-input.txt");
@@ -271,6 +319,15 @@ fn run_day07_part2() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "day07-table")]
+fn run_day07_table() -> anyhow::Result<()> {
+    let input_strings = include_str!("../inputs/day07-input.txt");
+    let rules = day07::camel_cards::Rules::jokers();
+    let rows = day07::table::ranked_table(&rules, input_strings)?;
+    println!("daily07-table =\n{}", day07::table::to_text(&rows));
+    Ok(())
+}
+
 #[cfg(feature = "day08-part1")]
 fn run_day08_part1() -> anyhow::Result<()> {
     let input_strings = include_str!("../inputs/day08-input.txt");
@@ -327,18 +384,45 @@ fn run_day10_part2() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "day10-part2-flood")]
+fn run_day10_part2_flood() -> anyhow::Result<()> {
+    let input_strings = include_str!("../inputs/day10-input.txt");
+    let result = day10::part2_flood::process_data(input_strings)?;
+    println!("daily10-part2-flood = {}", result); // 401
+    Ok(())
+}
+
+#[cfg(feature = "day10-render")]
+fn run_day10_render() -> anyhow::Result<()> {
+    let input_strings = include_str!("../inputs/day10-input.txt");
+    let result = day10::render::render(input_strings)?;
+    println!("daily10-render =\n{}", result);
+    Ok(())
+}
+
+#[cfg(feature = "day10-render-svg")]
+fn run_day10_render_svg() -> anyhow::Result<()> {
+    let input_strings = include_str!("../inputs/day10-input.txt");
+    // bare SVG on stdout, to be redirected to a file
+    let result = day10::render::render_svg(input_strings, 4)?;
+    println!("{}", result);
+    Ok(())
+}
+
 #[cfg(feature = "day11-part1")]
-fn run_day11_part1() {
+fn run_day11_part1() -> anyhow::Result<()> {
     let input_strings = include_str!("../inputs/day11-input.txt");
-    let result = day11::part1::process_data(input_strings);
+    let result = day11::part1::process_data(input_strings)?;
     println!("daily11-part1 = {}", result); // 9418609
+    Ok(())
 }
 
 #[cfg(feature = "day11-part2")]
-fn run_day11_part2() {
+fn run_day11_part2() -> anyhow::Result<()> {
     let input_strings = include_str!("../inputs/day11-input.txt");
-    let result = day11::part2::process_data(input_strings, 1000000);
+    let result = day11::part2::process_data(input_strings, 1000000)?;
     println!("daily11-part2 = {}", result); // 593821230983
+    Ok(())
 }
 
 #[cfg(feature = "day12-part1")]
@@ -358,31 +442,46 @@ fn run_day12_part2() -> anyhow::Result<()> {
 }
 
 #[cfg(feature = "day13-part1")]
-fn run_day13_part1() {
+fn run_day13_part1() -> anyhow::Result<()> {
     let input_strings = include_str!("../inputs/day13-input.txt");
-    let result = day13::part1::process_data(input_strings);
+    let result = day13::part1::process_data(input_strings)?;
     println!("daily13-part1 = {}", result); // 37113
+    Ok(())
 }
 
 #[cfg(feature = "day13-part2")]
-fn run_day13_part2() {
+fn run_day13_part2() -> anyhow::Result<()> {
     let input_strings = include_str!("../inputs/day13-input.txt");
-    let result = day13::part2::process_data(input_strings);
+    let result = day13::part2::process_data(input_strings)?;
     println!("daily13-part2 = {}", result); // 30449
+    Ok(())
 }
 
 #[cfg(feature = "day14-part1")]
-fn run_day14_part1() {
+fn run_day14_part1() -> anyhow::Result<()> {
     let input_strings = include_str!("../inputs/day14-input.txt");
-    let result = day14::part1::process_data(input_strings);
+    let result = day14::part1::process_data(input_strings)?;
     println!("daily14-part1 = {}", result); // 109654
+    Ok(())
 }
 
 #[cfg(feature = "day14-part2")]
-fn run_day14_part2() {
+fn run_day14_part2() -> anyhow::Result<()> {
     let input_strings = include_str!("../inputs/day14-input.txt");
-    let result = day14::part2::process_data(input_strings);
+    let result = day14::part2::process_data(input_strings)?;
     println!("daily14-part2 = {}", result); // 94876
+    Ok(())
+}
+
+#[cfg(feature = "day14-animate")]
+fn run_day14_animate() -> anyhow::Result<()> {
+    let input_strings = include_str!("../inputs/day14-input.txt");
+    // the frames are written as PPM images, the only format supported
+    let dir = std::env::var_os("DAY14_FRAMES_DIR")
+        .ok_or_else(|| anyhow::anyhow!("Set DAY14_FRAMES_DIR to the directory for the frames"))?;
+    let result = day14::animation::animate(input_strings, std::path::Path::new(&dir))?;
+    println!("daily14-animate = {}", result);
+    Ok(())
 }
 
 #[cfg(feature = "day15-part1")]
@@ -393,10 +492,11 @@ fn run_day15_part1() {
 }
 
 #[cfg(feature = "day15-part2")]
-fn run_day15_part2() {
+fn run_day15_part2() -> anyhow::Result<()> {
     let input_strings = include_str!("../inputs/day15-input.txt");
-    let result = day15::part2::process_data(input_strings);
+    let result = day15::part2::process_data(input_strings)?;
     println!("daily15-part2 = {}", result); // 268497
+    Ok(())
 }
 
 #[cfg(feature = "day16-part1")]